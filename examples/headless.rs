@@ -0,0 +1,42 @@
+use mozart::{
+    game::Game,
+    gl::headless::RecordingBackend,
+    obj::{sprite::Sprite, Make, Obj, Update},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene {
+    sprite: Sprite,
+    frames: u32,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            sprite: Sprite::make(game, Sprite::cfg_from_texture("examples/assets/sprite.png"))?,
+            frames: 0,
+        })
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, _game: &mut Game, _delta: f32) {
+        self.frames += 1;
+    }
+}
+
+fn main() -> Result<()> {
+    let mut game = Game::new().headless::<Scene>()?;
+    game.step_frames(10, 1. / 60.);
+
+    let scene = game.scene::<Scene>().unwrap();
+    println!("updated {} times", scene.frames);
+
+    let backend = game.graphics().backend::<RecordingBackend>().unwrap();
+    let frame = backend.last_frame().unwrap();
+    println!("drew {} sprites last frame", frame.draw_calls.len());
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use mozart::{
     game::Game,
     math::transform::Transform,
-    obj::{sprite::Sprite, Make, Obj, Obj2d, Update},
+    obj::{sprite::Sprite, Make, Obj, Obj2d},
+    Result,
 };
 
 #[derive(Obj, Obj2d)]
@@ -13,15 +14,15 @@ struct Player {
 impl Make for Player {
     type Config = Transform;
 
-    fn make(game: &mut Game, transform: Self::Config) -> Self {
-        Self {
+    fn make(game: &mut Game, transform: Self::Config) -> Result<Self> {
+        Ok(Self {
             transform,
             sprite: Sprite::make(
                 game,
                 Sprite::cfg_from_texture("./examples/assets/sprite.png")
                     .transform(Transform::IDENTITY.scaled_uniform(4.).with_pivot((8., 8.))),
-            ),
-        }
+            )?,
+        })
     }
 }
 
@@ -33,17 +34,17 @@ struct Scene {
 impl Make for Scene {
     type Config = ();
 
-    fn make(game: &mut Game, _: Self::Config) -> Self {
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
         dbg!(game.window_size());
-        Self {
+        Ok(Self {
             player: Player::make(
                 game,
                 Transform::IDENTITY.with_offset(*game.window_size() / 2.),
-            ),
-        }
+            )?,
+        })
     }
 }
 
-fn main() {
-    Game::new().start::<Scene>();
+fn main() -> Result<()> {
+    Game::new().start::<Scene>()
 }
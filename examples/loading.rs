@@ -0,0 +1,61 @@
+use mozart::{
+    game::{
+        assets::{texture::Texture, AssetGroup},
+        Game,
+    },
+    math::transform::Transform,
+    obj::{sprite::Sprite, Make, Obj, Update},
+    Result,
+};
+
+/// Shows progress while the level's textures load in the background.
+#[derive(Obj)]
+struct Loading {
+    group: AssetGroup,
+}
+
+impl Make for Loading {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        let mut group = AssetGroup::new();
+        group.add(game.load_gl_asset_async::<Texture>("examples/assets/sprite.png"));
+        Ok(Self { group })
+    }
+}
+
+impl Update for Loading {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        println!("loading: {:.0}%", self.group.progress() * 100.);
+        if self.group.is_done() {
+            for err in self.group.errors() {
+                eprintln!("{err}");
+            }
+            game.change_scene::<Level>();
+        }
+    }
+}
+
+#[derive(Obj)]
+struct Level {
+    sprite: Sprite,
+}
+
+impl Make for Level {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        // Already loaded, so this doesn't touch the disk
+        Ok(Self {
+            sprite: Sprite::make(
+                game,
+                Sprite::cfg_from_texture("examples/assets/sprite.png")
+                    .transform(Transform::IDENTITY.scaled_uniform(16.)),
+            )?,
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    Game::new().start::<Loading>()
+}
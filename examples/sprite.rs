@@ -1,9 +1,16 @@
 use mozart::{
-    game::Game,
+    game::{
+        assets::{asset, texture::Texture, AssetId},
+        Game,
+    },
     math::transform::Transform,
     obj::{sprite::Sprite, Make, Obj},
+    Result,
 };
 
+/// Embedded in release builds, which then run without the assets folder.
+const SPRITE: AssetId<Texture> = asset!("examples/assets/sprite.png");
+
 #[derive(Obj)]
 struct Scene {
     sprite: Sprite,
@@ -12,17 +19,16 @@ struct Scene {
 impl Make for Scene {
     type Config = ();
 
-    fn make(game: &mut Game, _: Self::Config) -> Self {
-        Self {
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
             sprite: Sprite::make(
                 game,
-                Sprite::cfg_from_texture("examples/assets/sprite.png")
-                    .transform(Transform::IDENTITY.scaled_uniform(16.)),
-            ),
-        }
+                Sprite::cfg_from_texture(SPRITE).transform(Transform::IDENTITY.scaled_uniform(16.)),
+            )?,
+        })
     }
 }
 
-fn main() {
+fn main() -> Result<()> {
     Game::new().start::<Scene>()
 }
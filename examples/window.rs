@@ -2,6 +2,7 @@ use mozart::{
     game::Game,
     gl::GraphicsContext,
     obj::{Draw, Make, Obj},
+    Result,
 };
 
 #[derive(Obj)]
@@ -10,8 +11,8 @@ struct TestScene;
 impl Make for TestScene {
     type Config = ();
 
-    fn make(_game: &mut Game, _config: Self::Config) -> Self {
-        Self
+    fn make(_game: &mut Game, _config: Self::Config) -> Result<Self> {
+        Ok(Self)
     }
 }
 
@@ -19,6 +20,6 @@ impl Draw for TestScene {
     fn draw(&self, _ctx: &mut GraphicsContext) {}
 }
 
-fn main() {
+fn main() -> Result<()> {
     Game::new().start::<TestScene>()
 }
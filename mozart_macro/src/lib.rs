@@ -2,9 +2,12 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Index};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, Index, LitStr, Token,
+};
 
-#[proc_macro_derive(Obj)]
+#[proc_macro_derive(Obj, attributes(process_mode))]
 pub fn derive_obj(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -14,6 +17,11 @@ pub fn derive_obj(input: TokenStream) -> TokenStream {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     // Get crate name
     let mozart = import_mozart();
+    // When to update, from #[process_mode(..)]
+    let process_mode = match process_mode(&input.attrs) {
+        Ok(mode) => mode,
+        Err(err) => return err.into_compile_error().into(),
+    };
 
     // Draw each child after drawing self
     let fields = fields(input.data);
@@ -27,12 +35,69 @@ pub fn derive_obj(input: TokenStream) -> TokenStream {
                 #(#mozart::maybe_draw_children!(&self.#fields, ctx);)*
             }
 
-            fn update_children(&mut self, game: &mut #mozart::game::Game, delta: f32) {
+            fn update_children(&mut self, game: &mut #mozart::game::Game, _delta: f32) {
+                // Skip self and children if paused, depending on process mode
+                let mode = #mozart::game::time::ProcessMode::#process_mode;
+                let Some(parent_mode) = game.enter_process_mode(mode) else {
+                    return;
+                };
+                // Objects that run while paused get unscaled time, so the
+                // delta from the parent isn't used
+                let delta = game.process_delta();
+
                 // Update children
                 #(#mozart::maybe_update_children!(&mut self.#fields, game, delta);)*
 
                 use #mozart::obj::maybe::MaybeUpdate;
                 (&mut &mut #mozart::obj::maybe::Wrapper(self)).maybe_update(game, delta);
+
+                game.exit_process_mode(parent_mode);
+            }
+
+            fn fixed_update_children(&mut self, game: &mut #mozart::game::Game, delta: f32) {
+                // Skip self and children if paused, depending on process mode
+                let mode = #mozart::game::time::ProcessMode::#process_mode;
+                let Some(parent_mode) = game.enter_process_mode(mode) else {
+                    return;
+                };
+
+                // Update children
+                #(#mozart::maybe_fixed_update_children!(&mut self.#fields, game, delta);)*
+
+                use #mozart::obj::maybe::MaybeFixedUpdate;
+                (&mut &mut #mozart::obj::maybe::Wrapper(self)).maybe_fixed_update(game, delta);
+
+                game.exit_process_mode(parent_mode);
+            }
+
+            fn ready_children(&mut self, game: &mut #mozart::game::Game) {
+                // Children are ready before their parent
+                #(#mozart::maybe_ready_children!(&mut self.#fields, game);)*
+
+                use #mozart::obj::maybe::MaybeReady;
+                (&mut &mut #mozart::obj::maybe::Wrapper(self)).maybe_ready(game);
+            }
+
+            fn exit_children(&mut self, game: &mut #mozart::game::Game) {
+                // Parents exit before their children
+                use #mozart::obj::maybe::MaybeExit;
+                (&mut &mut #mozart::obj::maybe::Wrapper(&mut *self)).maybe_exit(game);
+
+                #(#mozart::maybe_exit_children!(&mut self.#fields, game);)*
+            }
+
+            fn resize_children(&mut self, game: &mut #mozart::game::Game, size: #mozart::math::point::Pt2) {
+                #(#mozart::maybe_resize_children!(&mut self.#fields, game, size);)*
+
+                use #mozart::obj::maybe::MaybeResize;
+                (&mut &mut #mozart::obj::maybe::Wrapper(self)).maybe_resize(game, size);
+            }
+
+            fn focus_children(&mut self, game: &mut #mozart::game::Game, focused: bool) {
+                #(#mozart::maybe_focus_children!(&mut self.#fields, game, focused);)*
+
+                use #mozart::obj::maybe::MaybeFocus;
+                (&mut &mut #mozart::obj::maybe::Wrapper(self)).maybe_focus(game, focused);
             }
         }
     })
@@ -62,6 +127,76 @@ pub fn derive_obj_2d(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Arguments of `asset!`: a path, and optionally `embed`.
+struct AssetArgs {
+    path: LitStr,
+    embed: bool,
+}
+
+impl Parse for AssetArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut embed = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let option: Ident = input.parse()?;
+            if option != "embed" {
+                return Err(syn::Error::new(option.span(), "expected `embed`"));
+            }
+            embed = true;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { path, embed })
+    }
+}
+
+/// A typed `AssetId` for a file, relative to the package. Fails to compile if
+/// the file doesn't exist. The file is embedded in release builds, or always
+/// with `asset!("path", embed)`.
+#[proc_macro]
+pub fn asset(input: TokenStream) -> TokenStream {
+    let AssetArgs { path, embed } = parse_macro_input!(input as AssetArgs);
+    let mozart = import_mozart();
+
+    // Paths are relative to the package, like they are at runtime when run
+    // with cargo
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&root).join(path.value());
+    if !full_path.is_file() {
+        let message = format!("asset file `{}` does not exist", full_path.display());
+        return syn::Error::new(path.span(), message)
+            .into_compile_error()
+            .into();
+    }
+    let full_path = full_path.to_string_lossy().into_owned();
+
+    // Embedded in release builds, unless asked to always embed
+    let embed_cfg = if embed {
+        quote!(all())
+    } else {
+        quote!(not(debug_assertions))
+    };
+
+    TokenStream::from(quote! {{
+        #[cfg(#embed_cfg)]
+        const BYTES: ::core::option::Option<&'static [u8]> =
+            ::core::option::Option::Some(include_bytes!(#full_path));
+        #[cfg(not(#embed_cfg))]
+        const BYTES: ::core::option::Option<&'static [u8]> = ::core::option::Option::None;
+
+        #mozart::game::assets::AssetId::new(#path, BYTES)
+    }})
+}
+
+fn process_mode(attrs: &[Attribute]) -> syn::Result<Ident> {
+    let mut mode = Ident::new("Inherit", Span::call_site());
+    for attr in attrs {
+        if attr.path().is_ident("process_mode") {
+            mode = attr.parse_args()?;
+        }
+    }
+    Ok(mode)
+}
+
 fn fields(data: Data) -> Vec<proc_macro2::TokenStream> {
     match data {
         Data::Struct(ref data) => match data.fields {
@@ -0,0 +1,112 @@
+use std::{fmt, io};
+
+use miniquad::ShaderError;
+
+use crate::game::assets::AssetError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Anything that can go wrong while starting or running a game.
+///
+/// Its `Debug` output is the message followed by everything that caused it,
+/// so returning it from `main` prints something readable.
+pub enum Error {
+    /// A file couldn't be read or written.
+    Io { path: String, source: io::Error },
+    /// An asset couldn't be loaded.
+    Asset(AssetError),
+    /// A text file, like saved input bindings, isn't in the right format.
+    Parse {
+        what: &'static str,
+        line: usize,
+        message: String,
+    },
+    /// A replayed [`Recording`](crate::game::input::Recording) stopped
+    /// matching its checksums on `frame`, counting from 0.
+    ReplayDiverged {
+        frame: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// A shader failed to compile.
+    Shader(ShaderError),
+    /// The platform's graphics API isn't supported.
+    UnsupportedGraphics(&'static str),
+    /// Any other error, usually from game code.
+    Other(Box<dyn std::error::Error>),
+}
+
+impl Error {
+    /// Wrap an error or message from game code.
+    pub fn other(error: impl Into<Box<dyn std::error::Error>>) -> Self {
+        Self::Other(error.into())
+    }
+
+    /// Print the error and everything that caused it to stderr.
+    pub(crate) fn report(&self) {
+        eprintln!("error: {self:?}");
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")?;
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            write!(f, "\n  caused by: {cause}")?;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, .. } => write!(f, "could not access file {path}"),
+            Error::Asset(err) => err.fmt(f),
+            Error::Parse {
+                what,
+                line,
+                message,
+            } => write!(f, "invalid {what} on line {line}: {message}"),
+            Error::ReplayDiverged {
+                frame,
+                expected,
+                found,
+            } => write!(
+                f,
+                "replay diverged on frame {frame}: expected checksum {expected}, found {found}"
+            ),
+            Error::Shader(err) => write!(f, "failed to compile shader: {err}"),
+            Error::UnsupportedGraphics(api) => write!(f, "{api} is not supported yet"),
+            Error::Other(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Asset(err) => err.source(),
+            Error::Other(err) => err.source(),
+            Error::Parse { .. }
+            | Error::ReplayDiverged { .. }
+            | Error::Shader(_)
+            | Error::UnsupportedGraphics(_) => None,
+        }
+    }
+}
+
+impl From<AssetError> for Error {
+    fn from(err: AssetError) -> Self {
+        Self::Asset(err)
+    }
+}
+
+impl From<ShaderError> for Error {
+    fn from(err: ShaderError) -> Self {
+        Self::Shader(err)
+    }
+}
@@ -1,85 +1,179 @@
-use std::sync::Arc;
+use std::{any::Any, sync::Arc};
 
-use assets::{Asset, Assets, GlAsset};
-use input::Input;
-use miniquad::{conf::Conf, date, window::dpi_scale, EventHandler, KeyCode, MouseButton};
+use assets::{Asset, AssetError, AssetId, Assets, GlAsset, Handle, Vfs};
+use error::{ErrorSlot, NoGame};
+use event::Events;
+use input::{
+    record::Recorder, GamepadBackend, Input, InputEvent, KeyCode, KeyMods, MouseButton,
+    VirtualGamepads,
+};
+use miniquad::{date, EventHandler};
+use server::{Server, Servers};
+use time::Time;
+use timer::Timers;
+use window::{Window, WindowConf};
 
 use crate::{
-    gl::GraphicsContext,
+    gl::{backend::Backend, headless::RecordingBackend, GraphicsContext},
     math::{
         color::Color,
         point::{pt2, pt2i, Pt2, Pt2i},
+        Seconds,
     },
     obj::{MakeDefault, Obj},
+    Error, Result,
 };
 
 pub mod assets;
+mod error;
+mod event;
 pub mod input;
+mod scene;
+pub mod server;
+pub mod time;
+pub mod timer;
+pub mod window;
 
 pub struct Game {
     clear_color: Color,
     window_size: Pt2,
 
-    /// unix time in seconds since window launched
-    pub(crate) time_start: f64,
     /// unix time in seconds since last update() call
     pub(crate) last_frame: f64,
+    pub(crate) time: Time,
 
-    pub(crate) scene: Option<Box<dyn Obj>>,
+    /// scenes from bottom to top. only the top one is updated.
+    pub(crate) scenes: Vec<Box<dyn Obj>>,
+    pub(crate) scene_changes: Vec<scene::SceneChange>,
+    /// hooks for scenes that were busy when they were sent, by stack index
+    pub(crate) scene_hooks: Vec<(usize, scene::SceneHook)>,
     pub(crate) gl: GraphicsContext,
+    pub(crate) servers: Servers,
+    pub(crate) events: Events,
+    pub(crate) window: Window,
+    pub(crate) error: ErrorSlot,
+    pub(crate) recorder: Recorder,
+    pub(crate) gamepad_backend: Box<dyn GamepadBackend>,
 
     pub assets: Assets,
     pub input: Input,
+    pub timers: Timers,
 }
 
 pub struct GameBuilder {
     clear_color: Color,
     window_size: Pt2i,
     window_title: Option<String>,
+    fixed_timestep: Option<f32>,
+    servers: Vec<Box<dyn Server>>,
+    window: WindowConf,
+    gamepad_backend: Box<dyn GamepadBackend>,
+    vfs: Vfs,
 }
 
 impl Game {
+    /// Most fixed updates that can run in a single frame. If the game falls
+    /// further behind than this, the remaining time is dropped.
+    const MAX_FIXED_STEPS: u32 = 8;
+
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> GameBuilder {
         GameBuilder {
             clear_color: Color::BLACK,
             window_title: None,
             window_size: pt2i(800, 600),
+            fixed_timestep: None,
+            servers: Vec::new(),
+            window: WindowConf::default(),
+            gamepad_backend: Box::new(VirtualGamepads::new()),
+            vfs: Vfs::default(),
         }
     }
 }
 
 impl GameBuilder {
-    pub fn start<Scene>(self)
+    /// Open a window and run the game in it, returning once the window is
+    /// closed. If the game fails, the error is returned, so print it if
+    /// `main` doesn't return it. In debug builds the window stays open with
+    /// the error on a red screen until it is closed, other builds quit right
+    /// away.
+    pub fn start<Scene>(self) -> Result<()>
     where
         Scene: MakeDefault + 'static,
     {
-        miniquad::start(
-            Conf {
-                window_title: self.window_title.unwrap_or_else(String::new),
-                window_width: self.window_size.x,
-                window_height: self.window_size.y,
-
-                ..Default::default()
-            },
-            move || {
-                let mut game = Game {
-                    clear_color: self.clear_color,
-                    window_size: self.window_size.into(),
-
-                    time_start: date::now(),
-                    last_frame: date::now(),
-
-                    scene: None,
-                    gl: GraphicsContext::new().unwrap(),
-
-                    assets: Assets::new(),
-                    input: Input::new(),
-                };
-                game.scene = Some(Box::new(Scene::make_default(&mut game)));
-
-                Box::new(game)
-            },
-        );
+        let error = ErrorSlot::default();
+        let slot = error.clone();
+        miniquad::start(self.conf(), move || match GraphicsContext::new() {
+            Ok(gl) => Box::new(self.build::<Scene>(gl, false, slot)),
+            Err(err) => {
+                *slot.borrow_mut() = Some(err);
+                miniquad::window::order_quit();
+                Box::new(NoGame)
+            }
+        });
+
+        match error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Build a game that runs without a window or GPU, drawing to a
+    /// [`RecordingBackend`]. Advance it with [`Game::step`]. Fails if the
+    /// first scene can't be made.
+    pub fn headless<Scene>(self) -> Result<Game>
+    where
+        Scene: MakeDefault + 'static,
+    {
+        self.headless_with::<Scene>(RecordingBackend::new())
+    }
+
+    /// Build a game that runs without a window, drawing with `backend`.
+    pub fn headless_with<Scene>(self, backend: impl Backend) -> Result<Game>
+    where
+        Scene: MakeDefault + 'static,
+    {
+        let gl = GraphicsContext::with_backend(backend, self.window_size.into())?;
+        let mut game = self.build::<Scene>(gl, true, ErrorSlot::default());
+        match game.take_error() {
+            Some(err) => Err(err),
+            None => Ok(game),
+        }
+    }
+
+    fn build<Scene>(self, gl: GraphicsContext, headless: bool, error: ErrorSlot) -> Game
+    where
+        Scene: MakeDefault + 'static,
+    {
+        let mut game = Game {
+            clear_color: self.clear_color,
+            window_size: self.window_size.into(),
+
+            last_frame: date::now(),
+            time: Time::new(self.fixed_timestep.map(|hz| 1. / hz)),
+
+            scenes: Vec::new(),
+            scene_changes: Vec::new(),
+            scene_hooks: Vec::new(),
+            gl,
+            servers: Servers::new(),
+            events: Events::new(),
+            window: Window::new(&self.window, headless),
+            error,
+            recorder: Recorder::default(),
+            gamepad_backend: self.gamepad_backend,
+
+            assets: Assets::new(self.vfs),
+            input: Input::new(),
+            timers: Timers::new(),
+        };
+        for server in self.servers {
+            game.servers.insert(server);
+        }
+        game.push_scene::<Scene>();
+        game.apply_scene_changes();
+
+        game
     }
 
     pub fn clear_color(mut self, color: Color) -> Self {
@@ -90,62 +184,281 @@ impl GameBuilder {
         self.window_size = size.into();
         self
     }
+    /// Title of the window. It can't be changed once the game starts, since
+    /// miniquad 0.4 has no way to retitle a window.
     pub fn window_title(mut self, title: impl Into<String>) -> Self {
         self.window_title = Some(title.into());
         self
     }
+    /// Run [`FixedUpdate`](crate::obj::FixedUpdate) `hz` times per second,
+    /// independent of the frame rate. [`Update`](crate::obj::Update) still
+    /// runs once per frame, and draws can use
+    /// [`GraphicsContext::alpha`] to interpolate between fixed updates.
+    pub fn fixed_timestep(mut self, hz: f32) -> Self {
+        assert!(hz > 0., "fixed timestep must be positive");
+        self.fixed_timestep = Some(hz);
+        self
+    }
+    /// Read gamepads with `backend`. Defaults to a [`VirtualGamepads`] with
+    /// nothing connected.
+    pub fn gamepad_backend(mut self, backend: impl GamepadBackend) -> Self {
+        self.gamepad_backend = Box::new(backend);
+        self
+    }
+    /// Read asset files through `vfs`. Defaults to the current directory.
+    pub fn vfs(mut self, vfs: Vfs) -> Self {
+        self.vfs = vfs;
+        self
+    }
 }
 
 impl Game {
     pub fn window_size(&self) -> &Pt2 {
         &self.window_size
     }
-    pub fn time(&self) -> f32 {
-        (self.time_start - date::now()) as f32
+    /// The scene on top of the stack, if it is an `S`. A scene is borrowed
+    /// while its own hooks run, so this is `None` when called from inside the
+    /// top scene, such as from one of its objects' updates.
+    pub fn scene<S: Obj>(&self) -> Option<&S> {
+        let scene: &dyn Any = self.scenes.last()?.as_ref();
+        scene.downcast_ref()
+    }
+    /// The scene on top of the stack, if it is an `S`. See [`Game::scene`].
+    pub fn scene_mut<S: Obj>(&mut self) -> Option<&mut S> {
+        let scene: &mut dyn Any = self.scenes.last_mut()?.as_mut();
+        scene.downcast_mut()
+    }
+    pub fn graphics(&self) -> &GraphicsContext {
+        &self.gl
+    }
+
+    /// Run one update and draw, as if `delta` seconds of real time had passed
+    /// since the last frame. Meant for games built with [`GameBuilder::headless`].
+    pub fn step(&mut self, delta: Seconds) {
+        self.tick(delta);
+        self.draw();
+    }
+    /// Run `frames` updates and draws of `delta` seconds each.
+    pub fn step_frames(&mut self, frames: usize, delta: Seconds) {
+        for _ in 0..frames {
+            self.step(delta);
+        }
+    }
+
+    fn resize(&mut self, size: Pt2) {
+        self.window_size = size;
+        self.gl.update_viewport_transform(self.window_size);
+        // The mouse stays put in the window, but may have moved in the world
+        self.set_mouse_position(self.input.mouse_position());
+        self.for_each_scene(move |scene, game| scene.resize_children(game, size));
+    }
+
+    fn tick(&mut self, unscaled_delta: Seconds) {
+        if self.has_quit() {
+            // The game may have quit between frames, such as a headless
+            // game quitting between steps
+            self.exit_if_quit();
+            return;
+        }
+
+        let delta = self.time.advance(unscaled_delta);
+        self.assets.finish_loads(&mut self.gl, false);
+        #[cfg(feature = "hot-reload")]
+        self.reload_changed_assets();
+        self.poll_gamepads();
+        self.input.begin_frame(
+            self.time.unscaled_elapsed(),
+            self.time.fixed_delta.is_some(),
+        );
+        self.events.swap();
+        self.pre_update_servers();
+        self.update_timers(delta);
+
+        if let Some(top) = self.scenes.len().checked_sub(1) {
+            self.with_scene(top, |scene, game| {
+                if let Some(step) = game.time.fixed_delta {
+                    // Fixed updates hold while paused
+                    if !game.time.is_paused() {
+                        game.time.accumulator += delta;
+                    }
+
+                    let mut steps = 0;
+                    while game.time.accumulator >= step {
+                        if steps == Self::MAX_FIXED_STEPS {
+                            game.time.accumulator %= step;
+                            break;
+                        }
+                        game.input.begin_fixed_step();
+                        scene.fixed_update_children(game, step);
+                        game.input.end_fixed_step();
+                        game.time.accumulator -= step;
+                        steps += 1;
+                    }
+
+                    game.gl.set_alpha(game.time.accumulator / step);
+                }
+
+                scene.update_children(game, delta);
+            });
+        } else {
+            self.fail(Error::other("no scene to update, the last one was popped"));
+        }
+
+        self.post_update_servers();
+        self.apply_scene_changes();
+        self.end_recorded_frame(unscaled_delta);
+        self.exit_if_quit();
     }
 
     /// Load asset to GPU. This is used for textures and shaders.
+    ///
+    /// In debug builds with a window, assets that fail to load are reported
+    /// to stderr and replaced with a placeholder, like a magenta checkerboard
+    /// for textures, if their type has one.
+    #[expect(private_bounds)]
+    pub fn load_gl_asset<T: GlAsset>(&mut self, id: impl Into<AssetId<T>>) -> Result<Arc<T>> {
+        match self.try_load_gl_asset(id) {
+            Err(err) if self.use_placeholders() => {
+                let placeholder = self.assets.placeholder_gl(&mut self.gl);
+                fall_back(err, placeholder)
+            }
+            result => Ok(result?),
+        }
+    }
+    /// Load asset to GPU, without falling back to a placeholder.
+    #[expect(private_bounds)]
+    pub fn try_load_gl_asset<T: GlAsset>(
+        &mut self,
+        id: impl Into<AssetId<T>>,
+    ) -> Result<Arc<T>, AssetError> {
+        self.assets.try_load_gl(id.into(), &mut self.gl)
+    }
+    /// Load asset. Equivelent to game.assets.load, but falls back to a
+    /// placeholder like [`Game::load_gl_asset`].
+    pub fn load_asset<T: Asset>(&mut self, id: impl Into<AssetId<T>>) -> Result<Arc<T>> {
+        match self.assets.try_load(id) {
+            Err(err) if self.use_placeholders() => fall_back(err, self.assets.placeholder()),
+            result => Ok(result?),
+        }
+    }
+
+    /// Start loading an asset to the GPU in the background. See
+    /// [`Assets::load_async`].
     #[expect(private_bounds)]
-    pub fn load_gl_asset<T: GlAsset>(&mut self, path: &'static str) -> Arc<T> {
-        self.assets.load_gl(path, &mut self.gl)
+    pub fn load_gl_asset_async<T: GlAsset>(&mut self, id: impl Into<AssetId<T>>) -> Handle<T> {
+        self.assets.load_gl_async(id.into())
+    }
+    /// Start loading an asset in the background. Equivelent to
+    /// game.assets.load_async
+    pub fn load_asset_async<T: Asset + Send>(&mut self, id: impl Into<AssetId<T>>) -> Handle<T> {
+        self.assets.load_async(id)
+    }
+    /// Block until every asset loading in the background is done. Background
+    /// loads otherwise finish at the start of a frame, so this is mostly
+    /// useful for headless games.
+    pub fn wait_for_assets(&mut self) {
+        self.assets.finish_loads(&mut self.gl, true);
+    }
+
+    fn use_placeholders(&self) -> bool {
+        cfg!(debug_assertions) && !self.window.is_headless()
     }
-    /// Load asset. Equivelent to game.assets.load
-    pub fn load_asset<T: Asset>(&mut self, path: &'static str) -> Arc<T> {
-        self.assets.load(path)
+}
+
+/// Use `placeholder` for an asset that failed to load, if there is one.
+fn fall_back<T>(err: AssetError, placeholder: Option<Arc<T>>) -> Result<Arc<T>> {
+    match placeholder {
+        Some(placeholder) => {
+            Error::Asset(err).report();
+            Ok(placeholder)
+        }
+        None => Err(err.into()),
     }
 }
 
 impl EventHandler for Game {
     fn update(&mut self) {
-        if let Some(mut scene) = self.scene.take() {
-            let now = date::now();
-            let delta = now - self.last_frame;
+        self.wait_for_frame();
 
-            scene.update_children(self, delta as f32);
+        let now = date::now();
+        let mut delta = (now - self.last_frame) as Seconds;
+        self.last_frame = now;
 
-            self.scene = Some(scene);
-            self.last_frame = now;
-        } else {
-            println!("failed to get scene")
+        if let Some(recorded) = self.next_playback_frame() {
+            delta = recorded;
         }
+        self.tick(delta);
     }
 
     fn draw(&mut self) {
         self.gl.start_frame(self.clear_color);
-        if let Some(scene) = self.scene.as_ref() {
+        for scene in &self.scenes {
             scene.draw_children(&mut self.gl)
         }
         self.gl.finish();
     }
 
     fn resize_event(&mut self, width: f32, height: f32) {
-        self.window_size = pt2(width, height);
-        self.gl.update_viewport_transform(self.window_size);
+        self.resize(pt2(width, height));
     }
 
-    fn key_down_event(&mut self, key: miniquad::KeyCode, _: miniquad::KeyMods, repeat: bool) {
+    fn window_minimized_event(&mut self) {
+        self.set_focused(false);
+    }
+
+    fn window_restored_event(&mut self) {
+        self.set_focused(true);
+    }
+
+    fn quit_requested_event(&mut self) {
+        if self.quit_requested() {
+            self.exit_scenes();
+        } else {
+            miniquad::window::cancel_quit();
+        }
+    }
+
+    fn files_dropped_event(&mut self) {
+        self.add_window_dropped_files();
+    }
+
+    fn key_down_event(&mut self, key: KeyCode, modifiers: KeyMods, repeat: bool) {
         if !repeat {
-            self.input.set_key_down(key)
+            self.window_input(InputEvent::KeyDown { key, modifiers });
         }
     }
+
+    fn key_up_event(&mut self, key: KeyCode, modifiers: KeyMods) {
+        self.window_input(InputEvent::KeyUp { key, modifiers });
+    }
+
+    fn char_event(&mut self, character: char, _: KeyMods, _: bool) {
+        self.window_input(InputEvent::Char(character));
+    }
+
+    fn mouse_motion_event(&mut self, x: f32, y: f32) {
+        self.window_input(InputEvent::MouseMove(pt2(x, y)));
+    }
+
+    fn mouse_wheel_event(&mut self, x: f32, y: f32) {
+        self.window_input(InputEvent::MouseWheel(pt2(x, y)));
+    }
+
+    fn mouse_button_down_event(&mut self, button: MouseButton, x: f32, y: f32) {
+        let position = pt2(x, y);
+        self.window_input(InputEvent::MouseDown { button, position });
+    }
+
+    fn mouse_button_up_event(&mut self, button: MouseButton, x: f32, y: f32) {
+        let position = pt2(x, y);
+        self.window_input(InputEvent::MouseUp { button, position });
+    }
+
+    fn touch_event(&mut self, phase: miniquad::TouchPhase, id: u64, x: f32, y: f32) {
+        self.window_input(InputEvent::Touch {
+            id,
+            phase: phase.into(),
+            position: pt2(x, y),
+        });
+    }
 }
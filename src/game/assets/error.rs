@@ -0,0 +1,40 @@
+use std::{error::Error, fmt, io};
+
+/// An asset that couldn't be loaded.
+#[derive(Debug)]
+pub struct AssetError {
+    pub path: String,
+    /// Type name of the asset.
+    pub asset: &'static str,
+    pub kind: AssetErrorKind,
+}
+
+#[derive(Debug)]
+pub enum AssetErrorKind {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The file was read, but isn't a valid asset of this type.
+    Decode(Box<dyn Error + Send + Sync>),
+    /// The asset was decoded, but the GPU didn't accept it.
+    Upload(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { path, asset, kind } = self;
+        match kind {
+            AssetErrorKind::Io(_) => write!(f, "could not read {asset} from {path}"),
+            AssetErrorKind::Decode(_) => write!(f, "failed to decode {asset} from {path}"),
+            AssetErrorKind::Upload(_) => write!(f, "failed to upload {asset} from {path}"),
+        }
+    }
+}
+
+impl Error for AssetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            AssetErrorKind::Io(err) => Some(err),
+            AssetErrorKind::Decode(err) | AssetErrorKind::Upload(err) => Some(err.as_ref()),
+        }
+    }
+}
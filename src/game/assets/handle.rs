@@ -0,0 +1,140 @@
+use std::{cell::RefCell, fmt, rc::Rc, sync::Arc};
+
+use super::AssetError;
+
+/// How far along an asset loading in the background is.
+pub enum LoadState<T> {
+    Loading,
+    Loaded(Arc<T>),
+    Failed(Rc<AssetError>),
+}
+
+impl<T> Clone for LoadState<T> {
+    fn clone(&self) -> Self {
+        match self {
+            LoadState::Loading => LoadState::Loading,
+            LoadState::Loaded(asset) => LoadState::Loaded(asset.clone()),
+            LoadState::Failed(err) => LoadState::Failed(err.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for LoadState<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadState::Loading => write!(f, "Loading"),
+            LoadState::Loaded(_) => write!(f, "Loaded"),
+            LoadState::Failed(err) => f.debug_tuple("Failed").field(err).finish(),
+        }
+    }
+}
+
+/// An asset loading in the background. The file is read and decoded on a
+/// worker thread, and anything that needs the GPU is finished on the main
+/// thread at the start of a frame. Clones share the same asset.
+pub struct Handle<T> {
+    path: &'static str,
+    state: Rc<RefCell<LoadState<T>>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Handle<T> {
+    pub(crate) fn new(path: &'static str, state: LoadState<T>) -> Self {
+        Self {
+            path,
+            state: Rc::new(RefCell::new(state)),
+        }
+    }
+    pub(crate) fn set(&self, state: LoadState<T>) {
+        *self.state.borrow_mut() = state;
+    }
+
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+    pub fn state(&self) -> LoadState<T> {
+        self.state.borrow().clone()
+    }
+    /// The asset, once it has loaded.
+    pub fn get(&self) -> Option<Arc<T>> {
+        match &*self.state.borrow() {
+            LoadState::Loaded(asset) => Some(asset.clone()),
+            _ => None,
+        }
+    }
+    /// Whether the asset has loaded or failed.
+    pub fn is_done(&self) -> bool {
+        !matches!(*self.state.borrow(), LoadState::Loading)
+    }
+}
+
+/// Something an [`AssetGroup`] can track, whatever type of asset it loads.
+trait Progress {
+    fn is_done(&self) -> bool;
+    fn error(&self) -> Option<Rc<AssetError>>;
+}
+
+impl<T> Progress for RefCell<LoadState<T>> {
+    fn is_done(&self) -> bool {
+        !matches!(*self.borrow(), LoadState::Loading)
+    }
+    fn error(&self) -> Option<Rc<AssetError>> {
+        match &*self.borrow() {
+            LoadState::Failed(err) => Some(err.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Assets loading together, like everything a level needs, to show progress
+/// on a loading screen and wait until they are all done.
+#[derive(Default)]
+pub struct AssetGroup {
+    handles: Vec<Rc<dyn Progress>>,
+}
+
+impl AssetGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track `handle` in the group, returning it.
+    pub fn add<T: 'static>(&mut self, handle: Handle<T>) -> Handle<T> {
+        self.handles.push(handle.state.clone());
+        handle
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+    /// Number of assets that have loaded or failed.
+    pub fn done(&self) -> usize {
+        self.handles.iter().filter(|h| h.is_done()).count()
+    }
+    /// How many of the assets are done, from 0 to 1. Empty groups are done.
+    pub fn progress(&self) -> f32 {
+        if self.handles.is_empty() {
+            return 1.;
+        }
+        self.done() as f32 / self.len() as f32
+    }
+    /// Whether every asset has loaded or failed.
+    pub fn is_done(&self) -> bool {
+        self.handles.iter().all(|h| h.is_done())
+    }
+    /// Errors of the assets that failed, in the order they were added.
+    pub fn errors(&self) -> Vec<Rc<AssetError>> {
+        self.handles.iter().filter_map(|h| h.error()).collect()
+    }
+}
@@ -0,0 +1,76 @@
+use std::{fmt, marker::PhantomData};
+
+/// Where to load an asset of type `T` from. Made with [`asset!`], which checks
+/// the file exists at compile time and embeds it in release builds, or from a
+/// path that is only checked when the asset loads.
+///
+/// ```
+/// use mozart::game::assets::{asset, texture::Texture, AssetId};
+///
+/// const SPRITE: AssetId<Texture> = asset!("examples/assets/sprite.png");
+/// const EMBEDDED: AssetId<Texture> = asset!("examples/assets/sprite.png", embed);
+///
+/// assert_eq!(SPRITE.path(), "examples/assets/sprite.png");
+/// assert_eq!(SPRITE.is_embedded(), cfg!(not(debug_assertions)));
+/// let bytes = std::fs::read("examples/assets/sprite.png").unwrap();
+/// assert_eq!(EMBEDDED.bytes(), Some(&bytes[..]));
+/// ```
+///
+/// Paths to files that don't exist fail to compile:
+///
+/// ```compile_fail
+/// use mozart::game::assets::{asset, texture::Texture, AssetId};
+///
+/// const MISSING: AssetId<Texture> = asset!("examples/assets/missing.png");
+/// ```
+///
+/// [`asset!`]: super::asset
+pub struct AssetId<T> {
+    path: &'static str,
+    bytes: Option<&'static [u8]>,
+    _asset: PhantomData<fn() -> T>,
+}
+
+impl<T> AssetId<T> {
+    /// An asset read from `path`, or from `bytes` if they were embedded.
+    pub const fn new(path: &'static str, bytes: Option<&'static [u8]>) -> Self {
+        Self {
+            path,
+            bytes,
+            _asset: PhantomData,
+        }
+    }
+
+    pub const fn path(&self) -> &'static str {
+        self.path
+    }
+    /// Contents of the file, if it is embedded in the binary.
+    pub const fn bytes(&self) -> Option<&'static [u8]> {
+        self.bytes
+    }
+    pub const fn is_embedded(&self) -> bool {
+        self.bytes.is_some()
+    }
+}
+
+impl<T> From<&'static str> for AssetId<T> {
+    fn from(path: &'static str) -> Self {
+        Self::new(path, None)
+    }
+}
+
+impl<T> Clone for AssetId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for AssetId<T> {}
+
+impl<T> fmt::Debug for AssetId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetId")
+            .field("path", &self.path)
+            .field("embedded", &self.is_embedded())
+            .finish()
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use super::Assets;
+use crate::gl::GraphicsContext;
+
+/// Work done on a worker thread, like reading and decoding a file.
+pub(crate) type Job = Box<dyn FnOnce() -> Box<dyn Any + Send> + Send>;
+/// Work done on the main thread with a job's result, like uploading it to the
+/// GPU. Gets `None` if the job panicked.
+pub(crate) type Finish =
+    Box<dyn FnOnce(Option<Box<dyn Any + Send>>, &mut Assets, &mut GraphicsContext)>;
+
+/// Most worker threads loading assets at once.
+const MAX_WORKERS: usize = 4;
+
+/// Runs jobs on a pool of worker threads, started on the first job.
+pub(crate) struct Loader {
+    jobs: Option<Sender<(u64, Job)>>,
+    results: Receiver<(u64, Option<Box<dyn Any + Send>>)>,
+    results_sender: Sender<(u64, Option<Box<dyn Any + Send>>)>,
+    /// jobs that haven't finished yet
+    running: HashMap<u64, Finish>,
+    next_id: u64,
+}
+
+impl Loader {
+    pub(crate) fn new() -> Self {
+        let (results_sender, results) = mpsc::channel();
+        Self {
+            jobs: None,
+            results,
+            results_sender,
+            running: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    fn start_workers(&mut self) -> &Sender<(u64, Job)> {
+        self.jobs.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel::<(u64, Job)>();
+            let receiver = Arc::new(Mutex::new(receiver));
+            let workers = thread::available_parallelism().map_or(1, |n| n.get());
+            for i in 0..workers.min(MAX_WORKERS) {
+                let receiver = receiver.clone();
+                let results = self.results_sender.clone();
+                thread::Builder::new()
+                    .name(format!("asset loader {i}"))
+                    .spawn(move || loop {
+                        // Stops once the loader is dropped
+                        let Ok((id, job)) = receiver.lock().unwrap().recv() else {
+                            break;
+                        };
+                        let result = panic::catch_unwind(AssertUnwindSafe(job)).ok();
+                        if results.send((id, result)).is_err() {
+                            break;
+                        }
+                    })
+                    .expect("failed to start asset loader thread");
+            }
+            sender
+        })
+    }
+
+    /// Run `job` on a worker, then `finish` on the main thread with its result.
+    pub(crate) fn spawn(&mut self, job: Job, finish: Finish) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.running.insert(id, finish);
+        self.start_workers()
+            .send((id, job))
+            .expect("asset loader threads stopped");
+    }
+
+    /// A finished job and what to do with its result. Waits for one if
+    /// `block` is set and jobs are still running.
+    pub(crate) fn take_finished(
+        &mut self,
+        block: bool,
+    ) -> Option<(Finish, Option<Box<dyn Any + Send>>)> {
+        if self.running.is_empty() {
+            return None;
+        }
+        let (id, result) = if block {
+            self.results.recv().ok()?
+        } else {
+            self.results.try_recv().ok()?
+        };
+        let finish = self.running.remove(&id)?;
+        Some((finish, result))
+    }
+}
@@ -1,118 +1,315 @@
 use std::{
     any::{self, Any, TypeId},
+    borrow::Cow,
     collections::HashMap,
-    error::Error,
-    fs,
+    io,
+    rc::Rc,
     sync::Arc,
 };
 
+pub use error::{AssetError, AssetErrorKind};
+pub use handle::{AssetGroup, Handle, LoadState};
+pub use id::AssetId;
+pub use mozart_macro::asset;
+#[cfg(feature = "hot-reload")]
+pub use reload::AssetReloaded;
+pub use vfs::Vfs;
+
 use crate::gl::GraphicsContext;
+use loader::Loader;
 
+mod error;
+mod handle;
+mod id;
+mod loader;
+#[cfg(feature = "hot-reload")]
+mod reload;
+pub mod shader;
 pub mod texture;
+pub mod vfs;
 
 pub struct Assets {
     caches: HashMap<TypeId, Box<dyn Any>>,
+    loader: Loader,
+    /// shared with loader threads, and copied if mounts change while they
+    /// use it
+    vfs: Arc<Vfs>,
+    #[cfg(feature = "hot-reload")]
+    watcher: reload::Watcher,
 }
 struct AssetCache<L> {
     loaded: HashMap<&'static str, Arc<L>>,
+    /// loading in the background
+    loading: HashMap<&'static str, Handle<L>>,
+    /// made the first time it is needed
+    placeholder: Option<Arc<L>>,
 }
 
 impl Assets {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(vfs: Vfs) -> Self {
         Assets {
             caches: HashMap::new(),
+            loader: Loader::new(),
+            vfs: Arc::new(vfs),
+            #[cfg(feature = "hot-reload")]
+            watcher: reload::Watcher::default(),
         }
     }
 
-    pub fn load<L>(&mut self, path: &'static str) -> Arc<L>
+    /// Where asset files are read from.
+    pub fn vfs(&self) -> &Vfs {
+        &self.vfs
+    }
+    /// Change what is mounted. Assets that were already loaded aren't
+    /// loaded again.
+    pub fn vfs_mut(&mut self) -> &mut Vfs {
+        Arc::make_mut(&mut self.vfs)
+    }
+
+    fn cache<L: 'static>(&mut self) -> &mut AssetCache<L> {
+        self.caches
+            .entry(TypeId::of::<L>())
+            .or_insert_with(|| {
+                Box::new(AssetCache::<L> {
+                    loaded: HashMap::new(),
+                    loading: HashMap::new(),
+                    placeholder: None,
+                })
+            })
+            .downcast_mut()
+            .expect("valid cache in asset loader")
+    }
+
+    /// Load an asset, or get it from the cache if it was loaded before.
+    /// Failed loads aren't cached, so they are tried again next time.
+    pub fn try_load<L>(&mut self, id: impl Into<AssetId<L>>) -> Result<Arc<L>, AssetError>
     where
         L: Asset,
     {
-        let key = TypeId::of::<L>();
-        let cache = self.caches.get_mut(&key);
-        let cache: &mut AssetCache<L> = if let Some(cache) = cache {
-            cache
-        } else {
-            self.caches.insert(
-                key,
-                Box::new(AssetCache::<L> {
-                    loaded: HashMap::new(),
-                }),
-            );
-            self.caches.get_mut(&key).unwrap()
+        let id = id.into();
+        #[cfg(feature = "hot-reload")]
+        self.watch(id, reload::reload_asset::<L>);
+
+        if let Some(asset) = self.cache::<L>().loaded.get(id.path()) {
+            return Ok(asset.clone());
+        }
+
+        let arc = Arc::new(load_file(&self.vfs, id)?);
+        self.cache::<L>().loaded.insert(id.path(), arc.clone());
+        Ok(arc)
+    }
+    /// Like [`Assets::try_load`], but with the crate's [`Error`](crate::Error).
+    pub fn load<L>(&mut self, id: impl Into<AssetId<L>>) -> crate::Result<Arc<L>>
+    where
+        L: Asset,
+    {
+        Ok(self.try_load(id)?)
+    }
+
+    /// Start loading an asset in the background. The handle is ready right
+    /// away if the asset was loaded before, and shared with loads of the same
+    /// asset that haven't finished yet.
+    pub fn load_async<L>(&mut self, id: impl Into<AssetId<L>>) -> Handle<L>
+    where
+        L: Asset + Send,
+    {
+        let id = id.into();
+        #[cfg(feature = "hot-reload")]
+        self.watch(id, reload::reload_asset::<L>);
+
+        self.start_load(id, load_file::<L>, |_, asset, _| Ok(asset))
+    }
+
+    /// Start a background load, unless the asset is loaded or already
+    /// loading. `decode` runs on a worker thread, and `finish` on the main
+    /// thread.
+    fn start_load<L, D>(
+        &mut self,
+        id: AssetId<L>,
+        decode: fn(&Vfs, AssetId<L>) -> Result<D, AssetError>,
+        finish: fn(&'static str, D, &mut GraphicsContext) -> Result<L, AssetError>,
+    ) -> Handle<L>
+    where
+        L: 'static,
+        D: Send + 'static,
+    {
+        let path = id.path();
+        let cache = self.cache::<L>();
+        if let Some(asset) = cache.loaded.get(path) {
+            return Handle::new(path, LoadState::Loaded(asset.clone()));
         }
-        .downcast_mut()
-        .expect("valid cache in asset loader");
-
-        if let Some(asset) = cache.loaded.get(&path) {
-            asset.clone()
-        } else {
-            let data = fs::read(path);
-            if let Ok(data) = data {
-                let arc = Arc::new(if let Ok(asset) = L::load(&data) {
-                    asset
-                } else {
-                    panic!(
-                        "Failed to parse {asset} from path {path}.",
-                        asset = any::type_name::<L>(),
-                    )
-                });
-                cache.loaded.insert(path, arc.clone());
-                arc
-            } else {
-                panic!("Could not read file {path}.")
-            }
+        if let Some(handle) = cache.loading.get(&path) {
+            return handle.clone();
+        }
+        let handle = Handle::new(path, LoadState::Loading);
+        cache.loading.insert(path, handle.clone());
+
+        let vfs = self.vfs.clone();
+        self.loader.spawn(
+            Box::new(move || Box::new(decode(&vfs, id))),
+            Box::new(move |decoded, assets, gl| {
+                let decoded = match decoded {
+                    Some(decoded) => *decoded
+                        .downcast::<Result<D, AssetError>>()
+                        .expect("asset job returned the wrong type"),
+                    None => Err(error::<L>(
+                        path,
+                        AssetErrorKind::Decode("panicked while decoding".into()),
+                    )),
+                };
+                let cache = assets.cache::<L>();
+                let Some(handle) = cache.loading.remove(&path) else {
+                    return;
+                };
+                match decoded.and_then(|data| finish(path, data, gl)) {
+                    Ok(asset) => {
+                        let asset = Arc::new(asset);
+                        cache.loaded.insert(path, asset.clone());
+                        handle.set(LoadState::Loaded(asset));
+                    }
+                    Err(err) => handle.set(LoadState::Failed(Rc::new(err))),
+                }
+            }),
+        );
+        handle
+    }
+
+    /// Finish background loads that are done decoding. With `wait`, blocks
+    /// until every load has finished.
+    pub(crate) fn finish_loads(&mut self, gl: &mut GraphicsContext, wait: bool) {
+        while let Some((finish, decoded)) = self.loader.take_finished(wait) {
+            finish(decoded, self, gl);
+        }
+    }
+
+    /// The asset to show in place of one that failed to load, if `L` has one.
+    pub fn placeholder<L>(&mut self) -> Option<Arc<L>>
+    where
+        L: Asset,
+    {
+        let cache = self.cache::<L>();
+        if cache.placeholder.is_none() {
+            cache.placeholder = L::placeholder().map(Arc::new);
         }
+        cache.placeholder.clone()
     }
 }
 
 pub trait Asset: 'static + Sized {
-    fn load(data: &[u8]) -> Result<Self, Box<dyn Error>>;
+    fn load(data: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Shown instead of assets that failed to load in debug builds, see
+    /// [`Game::load_asset`](crate::game::Game::load_asset).
+    fn placeholder() -> Option<Self> {
+        None
+    }
 }
 
 impl Assets {
-    pub(crate) fn load_gl<L>(&mut self, path: &'static str, gl: &mut GraphicsContext) -> Arc<L>
+    pub(crate) fn try_load_gl<L>(
+        &mut self,
+        id: AssetId<L>,
+        gl: &mut GraphicsContext,
+    ) -> Result<Arc<L>, AssetError>
     where
         L: GlAsset,
     {
-        let key = TypeId::of::<L>();
-        let cache = self.caches.get_mut(&key);
-        let cache: &mut AssetCache<L> = if let Some(cache) = cache {
-            cache
-        } else {
-            self.caches.insert(
-                key,
-                Box::new(AssetCache::<L> {
-                    loaded: HashMap::new(),
-                }),
-            );
-            self.caches.get_mut(&key).unwrap()
+        #[cfg(feature = "hot-reload")]
+        self.watch(id, reload::reload_gl_asset::<L>);
+
+        if let Some(asset) = self.cache::<L>().loaded.get(id.path()) {
+            return Ok(asset.clone());
         }
-        .downcast_mut()
-        .expect("valid cache in asset loader");
-
-        if let Some(asset) = cache.loaded.get(&path) {
-            asset.clone()
-        } else {
-            let data = fs::read(path);
-            if let Ok(data) = data {
-                let arc = Arc::new(if let Ok(asset) = L::load(&data, gl) {
-                    asset
-                } else {
-                    panic!(
-                        "Failed to parse {asset} from path {path}.",
-                        asset = any::type_name::<L>(),
-                    )
-                });
-                cache.loaded.insert(path, arc.clone());
-                arc
-            } else {
-                panic!("Could not read file {path}.")
-            }
+
+        let arc = Arc::new(upload::<L>(id.path(), decode_file(&self.vfs, id)?, gl)?);
+        self.cache::<L>().loaded.insert(id.path(), arc.clone());
+        Ok(arc)
+    }
+
+    /// Start loading an asset to the GPU in the background, like
+    /// [`Assets::load_async`]. It is uploaded on the main thread at the start
+    /// of a frame.
+    pub(crate) fn load_gl_async<L>(&mut self, id: AssetId<L>) -> Handle<L>
+    where
+        L: GlAsset,
+    {
+        #[cfg(feature = "hot-reload")]
+        self.watch(id, reload::reload_gl_asset::<L>);
+
+        self.start_load(id, decode_file::<L>, upload::<L>)
+    }
+
+    pub(crate) fn placeholder_gl<L>(&mut self, gl: &mut GraphicsContext) -> Option<Arc<L>>
+    where
+        L: GlAsset,
+    {
+        let cache = self.cache::<L>();
+        if cache.placeholder.is_none() {
+            cache.placeholder = L::placeholder(gl).map(Arc::new);
         }
+        cache.placeholder.clone()
     }
 }
 
+/// An asset that lives on the GPU. Loading is split in two, so the file can
+/// be decoded without the [`GraphicsContext`].
 pub(crate) trait GlAsset: 'static + Sized {
-    fn load(data: &[u8], gl: &mut GraphicsContext) -> Result<Self, Box<dyn Error>>;
+    /// The asset decoded from its file, before it is uploaded. Decoding can
+    /// happen on another thread.
+    type Data: Send + 'static;
+
+    fn decode(data: &[u8]) -> Result<Self::Data, Box<dyn std::error::Error + Send + Sync>>;
+    fn upload(
+        data: Self::Data,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn placeholder(_gl: &mut GraphicsContext) -> Option<Self> {
+        None
+    }
+
+    /// Make a new version of the asset after its file changed. Uploads a
+    /// new asset by default, but assets should reuse what objects already
+    /// hold on to when they can, like texture ids.
+    #[cfg(feature = "hot-reload")]
+    fn reload(
+        &self,
+        data: Self::Data,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::upload(data, gl)
+    }
+}
+
+/// The contents of an asset's file. Embedded assets are only used when no
+/// mount has the file, so mods can still replace them.
+fn read<L>(vfs: &Vfs, id: AssetId<L>) -> Result<Cow<'static, [u8]>, AssetError> {
+    match (vfs.read(id.path()), id.bytes()) {
+        (Ok(bytes), _) => Ok(Cow::Owned(bytes)),
+        (Err(err), Some(bytes)) if err.kind() == io::ErrorKind::NotFound => {
+            Ok(Cow::Borrowed(bytes))
+        }
+        (Err(err), _) => Err(error::<L>(id.path(), AssetErrorKind::Io(err))),
+    }
+}
+fn load_file<L: Asset>(vfs: &Vfs, id: AssetId<L>) -> Result<L, AssetError> {
+    L::load(&read(vfs, id)?).map_err(|err| error::<L>(id.path(), AssetErrorKind::Decode(err)))
+}
+fn decode_file<L: GlAsset>(vfs: &Vfs, id: AssetId<L>) -> Result<L::Data, AssetError> {
+    L::decode(&read(vfs, id)?).map_err(|err| error::<L>(id.path(), AssetErrorKind::Decode(err)))
+}
+fn upload<L: GlAsset>(
+    path: &'static str,
+    data: L::Data,
+    gl: &mut GraphicsContext,
+) -> Result<L, AssetError> {
+    L::upload(data, gl).map_err(|err| error::<L>(path, AssetErrorKind::Upload(err)))
+}
+
+pub(crate) fn error<L>(path: impl Into<String>, kind: AssetErrorKind) -> AssetError {
+    AssetError {
+        path: path.into(),
+        asset: any::type_name::<L>(),
+        kind,
+    }
 }
@@ -0,0 +1,120 @@
+use std::{any::TypeId, collections::HashMap, sync::Arc, time::SystemTime};
+
+use super::{
+    decode_file, error, load_file, Asset, AssetError, AssetErrorKind, AssetId, Assets, GlAsset,
+};
+use crate::{game::Game, gl::GraphicsContext, Error};
+
+/// Emitted when an asset was loaded again because its file changed. Objects
+/// holding on to the asset can load it again to get the new version, and
+/// rebuild anything made from it. Textures and shaders are updated in place,
+/// so sprites show the new image or shader without doing anything, even while
+/// paused. Only sent with the `hot-reload` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetReloaded {
+    pub path: &'static str,
+}
+
+type Reload = fn(&mut Assets, &'static str, &mut GraphicsContext) -> Result<(), AssetError>;
+
+/// Seconds between checks for changed files.
+const POLL_INTERVAL: f64 = 0.25;
+
+/// Files of loaded assets, to reload them when they change.
+#[derive(Default)]
+pub(crate) struct Watcher {
+    /// by path and type of asset, since a file can be loaded as several types
+    files: HashMap<(&'static str, TypeId), Watched>,
+    last_poll: f64,
+}
+
+struct Watched {
+    modified: Option<SystemTime>,
+    reload: Reload,
+}
+
+impl Assets {
+    /// Watch the file of an asset. Embedded assets aren't watched, since
+    /// they can't change.
+    pub(crate) fn watch<L: 'static>(&mut self, id: AssetId<L>, reload: Reload) {
+        if id.is_embedded() {
+            return;
+        }
+        let path = id.path();
+        let vfs = &self.vfs;
+        self.watcher
+            .files
+            .entry((path, TypeId::of::<L>()))
+            .or_insert_with(|| Watched {
+                modified: vfs.modified(path),
+                reload,
+            });
+    }
+
+    /// Reload assets whose files changed, returning their paths. Assets that
+    /// fail to reload are reported to stderr and keep their old version.
+    fn reload_changed(&mut self, now: f64, gl: &mut GraphicsContext) -> Vec<&'static str> {
+        if now - self.watcher.last_poll < POLL_INTERVAL {
+            return Vec::new();
+        }
+        self.watcher.last_poll = now;
+
+        let mut changed = Vec::new();
+        for (&(path, _), watched) in &mut self.watcher.files {
+            let modified = self.vfs.modified(path);
+            if modified != watched.modified {
+                watched.modified = modified;
+                changed.push((path, watched.reload));
+            }
+        }
+
+        let mut reloaded = Vec::new();
+        for (path, reload) in changed {
+            match reload(self, path, gl) {
+                Ok(()) => reloaded.push(path),
+                Err(err) => Error::Asset(err).report(),
+            }
+        }
+        reloaded.sort_unstable();
+        reloaded.dedup();
+        reloaded
+    }
+}
+
+pub(crate) fn reload_asset<L: Asset>(
+    assets: &mut Assets,
+    path: &'static str,
+    _gl: &mut GraphicsContext,
+) -> Result<(), AssetError> {
+    let asset = load_file::<L>(&assets.vfs, path.into())?;
+    assets.cache::<L>().loaded.insert(path, Arc::new(asset));
+    Ok(())
+}
+
+pub(crate) fn reload_gl_asset<L: GlAsset>(
+    assets: &mut Assets,
+    path: &'static str,
+    gl: &mut GraphicsContext,
+) -> Result<(), AssetError> {
+    let data = decode_file::<L>(&assets.vfs, path.into())?;
+    let cache = assets.cache::<L>();
+    // Still loading in the background, which will read the new file
+    let Some(old) = cache.loaded.get(&path) else {
+        return Ok(());
+    };
+    let asset = old
+        .reload(data, gl)
+        .map_err(|err| error::<L>(path, AssetErrorKind::Upload(err)))?;
+    cache.loaded.insert(path, Arc::new(asset));
+    Ok(())
+}
+
+impl Game {
+    /// Reload changed assets, and tell objects about them.
+    pub(crate) fn reload_changed_assets(&mut self) {
+        let now = self.time.unscaled_elapsed();
+        for path in self.assets.reload_changed(now, &mut self.gl) {
+            self.emit(AssetReloaded { path });
+        }
+    }
+}
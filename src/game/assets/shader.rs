@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::gl::{GraphicsContext, ShaderId};
+
+use super::{Asset, GlAsset};
+
+/// GLSL source of a shader, read from one file with a `#shader vertex` line
+/// before the vertex stage and a `#shader fragment` line before the fragment
+/// stage:
+///
+/// ```text
+/// #shader vertex
+/// #version 100
+/// attribute vec2 in_pos;
+/// ...
+///
+/// #shader fragment
+/// #version 100
+/// uniform sampler2D tex;
+/// ...
+/// ```
+///
+/// Shaders get the same inputs as the default one: `in_pos` and `in_uv`
+/// attributes, and a `tex` texture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderSource {
+    pub vertex: String,
+    pub fragment: String,
+}
+
+#[derive(Debug)]
+struct MissingStage(&'static str);
+
+impl fmt::Display for MissingStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing `#shader {}` stage", self.0)
+    }
+}
+impl std::error::Error for MissingStage {}
+
+impl Asset for ShaderSource {
+    fn load(data: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let text = std::str::from_utf8(data)?;
+        let (mut vertex, mut fragment) = (None, None);
+        // Lines before the first stage are ignored
+        let mut stage: Option<&mut Option<String>> = None;
+        for line in text.lines() {
+            match line.trim().strip_prefix("#shader") {
+                Some(name) => {
+                    stage = match name.trim() {
+                        "vertex" => Some(&mut vertex),
+                        "fragment" => Some(&mut fragment),
+                        name => return Err(format!("unknown shader stage `{name}`").into()),
+                    };
+                }
+                None => {
+                    if let Some(stage) = &mut stage {
+                        let source = stage.get_or_insert_with(String::new);
+                        source.push_str(line);
+                        source.push('\n');
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            vertex: vertex.ok_or(MissingStage("vertex"))?,
+            fragment: fragment.ok_or(MissingStage("fragment"))?,
+        })
+    }
+}
+
+pub struct Shader {
+    pub source: ShaderSource,
+    pub gl_shader: ShaderId,
+}
+
+impl GlAsset for Shader {
+    type Data = ShaderSource;
+
+    fn decode(data: &[u8]) -> Result<ShaderSource, Box<dyn std::error::Error + Send + Sync>> {
+        ShaderSource::load(data)
+    }
+    fn upload(
+        source: ShaderSource,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self {
+            gl_shader: gl.create_shader(&source.vertex, &source.fragment)?,
+            source,
+        })
+    }
+
+    /// Recompiles the shader in place, so everything drawing with it uses the
+    /// new version right away.
+    #[cfg(feature = "hot-reload")]
+    fn reload(
+        &self,
+        source: ShaderSource,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        gl.update_shader(self.gl_shader, &source.vertex, &source.fragment)?;
+        Ok(Self {
+            gl_shader: self.gl_shader,
+            source,
+        })
+    }
+}
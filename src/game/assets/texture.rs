@@ -1,5 +1,5 @@
 use crate::{
-    gl::GraphicsContext,
+    gl::{GraphicsContext, TextureId},
     math::{
         color::Color,
         point::{pt2i, Pt2i},
@@ -8,6 +8,7 @@ use crate::{
 
 use super::{Asset, GlAsset};
 
+#[derive(Clone)]
 pub struct Image {
     pub width: u32,
     pub height: u32,
@@ -25,6 +26,28 @@ impl Image {
         }
     }
 
+    /// A magenta and black checkerboard, to stand in for images that failed
+    /// to load.
+    pub fn placeholder() -> Self {
+        const SIZE: u32 = 8;
+        let magenta = Color::from_hex_rgb(0xff00ff);
+        Self {
+            width: SIZE,
+            height: SIZE,
+            bytes: (0..SIZE * SIZE)
+                .flat_map(|i| {
+                    let (x, y) = (i % SIZE, i / SIZE);
+                    let color = if (x + y) % 2 == 0 {
+                        magenta
+                    } else {
+                        Color::BLACK
+                    };
+                    Into::<[u8; 4]>::into(color)
+                })
+                .collect(),
+        }
+    }
+
     /// Size in pixels of texture
     pub fn size(&self) -> Pt2i {
         pt2i(self.width as i32, self.height as i32)
@@ -32,7 +55,7 @@ impl Image {
 }
 
 impl Asset for Image {
-    fn load(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    fn load(data: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let img = image::load_from_memory(data)?.into_rgba8();
 
         Ok(Self {
@@ -41,19 +64,49 @@ impl Asset for Image {
             bytes: img.into_raw(),
         })
     }
+
+    fn placeholder() -> Option<Self> {
+        Some(Self::placeholder())
+    }
 }
 
 pub struct Texture {
     pub image: Image,
-    pub gl_texture: miniquad::TextureId,
+    pub gl_texture: TextureId,
 }
 
 impl GlAsset for Texture {
-    fn load(data: &[u8], gl: &mut GraphicsContext) -> Result<Self, Box<dyn std::error::Error>> {
-        let image = Image::load(data)?;
+    type Data = Image;
+
+    fn decode(data: &[u8]) -> Result<Image, Box<dyn std::error::Error + Send + Sync>> {
+        Image::load(data)
+    }
+    fn upload(
+        image: Image,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         Ok(Self {
             gl_texture: gl.create_texture(&image),
             image,
         })
     }
+
+    fn placeholder(gl: &mut GraphicsContext) -> Option<Self> {
+        Self::upload(Image::placeholder(), gl).ok()
+    }
+
+    /// Updates the texture in place, so sprites drawing it show the new image
+    /// right away.
+    #[cfg(feature = "hot-reload")]
+    fn reload(
+        &self,
+        image: Image,
+        gl: &mut GraphicsContext,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        gl.update_texture(self.gl_texture, &image);
+        Ok(Self {
+            gl_texture: self.gl_texture,
+            image,
+        })
+    }
 }
@@ -0,0 +1,334 @@
+//! Where asset files are read from. Sources are mounted under a prefix, and
+//! a file is read from the mount with the highest priority that has it, so a
+//! mod folder mounted above the base assets can replace some of them.
+//! Absolute paths and paths that go above the root aren't in any mount, and
+//! are read straight from disk.
+//!
+//! ```ignore
+//! let mut vfs = Vfs::new();
+//! vfs.mount("", Dir::new("assets"), 0);
+//! vfs.mount("", Pack::open("base.pack")?, -1);
+//! vfs.mount("", Dir::new("mods/hats"), 10);
+//! Game::new().vfs(vfs).start::<Scene>()
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+pub use pack::{Pack, PackBuilder};
+
+mod pack;
+
+/// Files that can be mounted in a [`Vfs`]. Paths are relative to the mount,
+/// with `/` between components.
+pub trait Source: Send + Sync + 'static {
+    /// Read a file. Fails with [`io::ErrorKind::NotFound`] if the source
+    /// doesn't have it, so mounts below it are tried.
+    fn read(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    /// When a file last changed, for the `hot-reload` feature. `None` if the
+    /// source doesn't have it or can't tell.
+    fn modified(&self, _path: &str) -> Option<SystemTime> {
+        None
+    }
+}
+
+#[derive(Clone)]
+struct Mount {
+    prefix: String,
+    priority: i32,
+    source: Arc<dyn Source>,
+}
+
+/// Mounted sources that asset files are read from. Reads from the current
+/// directory by default.
+#[derive(Clone)]
+pub struct Vfs {
+    /// highest priority first
+    mounts: Vec<Mount>,
+}
+
+impl Vfs {
+    /// A file system with nothing mounted.
+    pub fn new() -> Self {
+        Self { mounts: Vec::new() }
+    }
+
+    /// Mount `source` under `prefix`, so `prefix/path` reads `path` from it.
+    /// Mounts with a higher priority are tried first, and a mount is tried
+    /// before earlier ones with the same priority.
+    pub fn mount(&mut self, prefix: &str, source: impl Source, priority: i32) {
+        let index = self
+            .mounts
+            .iter()
+            .position(|mount| mount.priority <= priority)
+            .unwrap_or(self.mounts.len());
+        self.mounts.insert(
+            index,
+            Mount {
+                prefix: normalize(prefix).expect("prefix leaves the file system"),
+                priority,
+                source: Arc::new(source),
+            },
+        );
+    }
+    /// Remove every mount under `prefix`, returning how many there were.
+    pub fn unmount(&mut self, prefix: &str) -> usize {
+        let prefix = normalize(prefix).unwrap_or_default();
+        let before = self.mounts.len();
+        self.mounts.retain(|mount| mount.prefix != prefix);
+        before - self.mounts.len()
+    }
+
+    /// Read a file from the first mount that has it. Absolute paths and
+    /// paths that go above the root, like `../shared/icon.png`, are read from
+    /// disk as they are.
+    pub fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let Some(path) = mounted(path) else {
+            return fs::read(path);
+        };
+        for (source, rest) in self.sources(&path) {
+            match source.read(rest) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "not found in any mount",
+        ))
+    }
+
+    /// When a file last changed, from the first mount that can tell.
+    pub fn modified(&self, path: &str) -> Option<SystemTime> {
+        let Some(path) = mounted(path) else {
+            return fs::metadata(path).and_then(|m| m.modified()).ok();
+        };
+        let modified = self
+            .sources(&path)
+            .find_map(|(source, rest)| source.modified(rest));
+        modified
+    }
+
+    /// Mounted sources that could have `path`, in order, with the path
+    /// relative to each.
+    fn sources<'a>(&'a self, path: &'a str) -> impl Iterator<Item = (&'a dyn Source, &'a str)> {
+        self.mounts.iter().filter_map(move |mount| {
+            let rest = match path.strip_prefix(mount.prefix.as_str())? {
+                rest if mount.prefix.is_empty() => rest,
+                "" => "",
+                rest => rest.strip_prefix('/')?,
+            };
+            Some((mount.source.as_ref(), rest))
+        })
+    }
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        let mut vfs = Self::new();
+        vfs.mount("", Dir::new("."), 0);
+        vfs
+    }
+}
+
+/// `path` relative to the mounts. `None` if it is absolute or goes above the
+/// root, so it can't be in any of them.
+fn mounted(path: &str) -> Option<String> {
+    if Path::new(path).has_root() {
+        return None;
+    }
+    normalize(path)
+}
+
+/// Turns `./a//b/../c` into `a/c`. `None` if the path goes above its root.
+fn normalize(path: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// A directory on disk.
+pub struct Dir {
+    root: PathBuf,
+}
+
+impl Dir {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+        }
+    }
+}
+
+impl Source for Dir {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.root.join(path))
+    }
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        fs::metadata(self.root.join(path))
+            .and_then(|m| m.modified())
+            .ok()
+    }
+}
+
+/// Files kept in memory, like ones embedded with `include_bytes!` or made by
+/// a test.
+#[derive(Default, Clone)]
+pub struct Memory {
+    files: HashMap<String, Arc<[u8]>>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: &str, bytes: impl Into<Arc<[u8]>>) {
+        let path = normalize(path).expect("path leaves the file system");
+        self.files.insert(path, bytes.into());
+    }
+    pub fn with(mut self, path: &str, bytes: impl Into<Arc<[u8]>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+}
+
+impl Source for Memory {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        match self.files.get(path) {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn read_string(vfs: &Vfs, path: &str) -> io::Result<String> {
+        vfs.read(path)
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+    }
+
+    #[test]
+    fn reads_memory_mounts_under_their_prefix() {
+        let mut vfs = Vfs::new();
+        vfs.mount("data", Memory::new().with("a/b.txt", &b"b"[..]), 0);
+
+        assert_eq!(read_string(&vfs, "data/a/b.txt").unwrap(), "b");
+        assert_eq!(read_string(&vfs, "./data//x/../a/b.txt").unwrap(), "b");
+        assert_eq!(
+            vfs.read("a/b.txt").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(vfs.unmount("data/"), 1);
+        assert!(vfs.read("data/a/b.txt").is_err());
+    }
+
+    #[test]
+    fn higher_priority_mounts_win() {
+        let mut vfs = Vfs::new();
+        vfs.mount(
+            "",
+            Memory::new()
+                .with("a", &b"base"[..])
+                .with("b", &b"base"[..]),
+            0,
+        );
+        vfs.mount("", Memory::new().with("a", &b"mod"[..]), 10);
+        vfs.mount("", Memory::new().with("a", &b"low"[..]), -1);
+
+        assert_eq!(read_string(&vfs, "a").unwrap(), "mod");
+        // Falls through to lower mounts for files a mount doesn't have
+        assert_eq!(read_string(&vfs, "b").unwrap(), "base");
+    }
+
+    #[test]
+    fn reads_absolute_and_parent_paths_from_disk() {
+        let mut vfs = Vfs::new();
+        vfs.mount("", Memory::new().with("Cargo.toml", &b"mounted"[..]), 0);
+        let manifest = fs::read_to_string("Cargo.toml").unwrap();
+
+        let absolute = env::current_dir().unwrap().join("Cargo.toml");
+        let absolute = absolute.to_str().unwrap();
+        assert_eq!(read_string(&vfs, absolute).unwrap(), manifest);
+        assert!(vfs.modified(absolute).is_some());
+
+        // Tests run in the crate's directory, so this leaves it and comes back
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).file_name().unwrap();
+        let parent = format!("../{}/Cargo.toml", crate_dir.to_str().unwrap());
+        assert_eq!(read_string(&vfs, &parent).unwrap(), manifest);
+    }
+
+    fn pack() -> PackBuilder {
+        let mut pack = PackBuilder::new();
+        pack.add("sprites/hat.png", &b"hat"[..]);
+        pack.add("./readme.txt", &b"read me"[..]);
+        pack
+    }
+
+    fn invalid_data<T>(result: io::Result<T>) -> bool {
+        matches!(result, Err(err) if err.kind() == io::ErrorKind::InvalidData)
+    }
+
+    #[test]
+    fn packs_round_trip() {
+        let pack = Pack::from_bytes(pack().to_bytes()).unwrap();
+        let mut paths: Vec<_> = pack.paths().collect();
+        paths.sort();
+        assert_eq!(paths, ["readme.txt", "sprites/hat.png"]);
+
+        let mut vfs = Vfs::new();
+        vfs.mount("base", pack, 0);
+        assert_eq!(read_string(&vfs, "base/sprites/hat.png").unwrap(), "hat");
+        assert_eq!(read_string(&vfs, "base/readme.txt").unwrap(), "read me");
+        assert!(vfs.read("base/missing").is_err());
+    }
+
+    #[test]
+    fn saved_packs_open() {
+        let path = env::temp_dir().join(format!("mozart-vfs-{}.pack", std::process::id()));
+        pack().save(&path).unwrap();
+        let pack = Pack::open(&path);
+        let read = pack.as_ref().map(|pack| pack.read("sprites/hat.png"));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap().unwrap(), b"hat");
+    }
+
+    #[test]
+    fn corrupt_packs_fail_to_open() {
+        let bytes = pack().to_bytes();
+        assert!(invalid_data(Pack::from_bytes(&b"not a pack"[..])));
+
+        // Missing the end of the last file
+        assert!(invalid_data(Pack::from_bytes(&bytes[..bytes.len() - 1])));
+
+        // A path longer than the whole pack
+        let mut long_path = bytes.clone();
+        long_path[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(invalid_data(Pack::from_bytes(long_path)));
+
+        // A file that would end past u64::MAX. Paths are sorted, so
+        // readme.txt is first, and its offset follows its 10 byte path
+        let mut overflow = bytes.clone();
+        overflow[26..34].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(invalid_data(Pack::from_bytes(overflow)));
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use super::{normalize, Source};
+
+/// Start of every pack file.
+const MAGIC: &[u8; 4] = b"MZPK";
+const VERSION: u32 = 1;
+
+/// Many files packed into one, made with a [`PackBuilder`]. Files are stored
+/// uncompressed after an index of their paths.
+///
+/// Packs can be read from disk, or embedded in the game with
+/// `Pack::from_bytes(&include_bytes!("assets.pack")[..])`.
+pub struct Pack {
+    data: Data,
+    entries: Entries,
+}
+
+/// Offset from the start of the data, and length, by path.
+type Entries = HashMap<String, (u64, u64)>;
+
+enum Data {
+    /// read from the file when needed
+    File {
+        path: PathBuf,
+        start: u64,
+    },
+    Bytes {
+        bytes: Arc<[u8]>,
+        start: u64,
+    },
+}
+
+impl Pack {
+    /// Read the index of a pack file. Files are read from it when they load.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let (entries, start) = read_index(&mut BufReader::new(file), len)?;
+        Ok(Self {
+            data: Data::File {
+                path: path.to_owned(),
+                start,
+            },
+            entries,
+        })
+    }
+
+    /// A pack kept in memory.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> io::Result<Self> {
+        let bytes = bytes.into();
+        let (entries, start) = read_index(&mut io::Cursor::new(&bytes[..]), bytes.len() as u64)?;
+        Ok(Self {
+            data: Data::Bytes { bytes, start },
+            entries,
+        })
+    }
+
+    /// Paths of the files in the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+}
+
+/// Read the index of a pack that is `len` bytes long, and where its data
+/// starts. Every entry is checked to be inside the data, so a damaged pack
+/// fails here instead of when its files are read.
+fn read_index(reader: &mut (impl Read + Seek), len: u64) -> io::Result<(Entries, u64)> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid("not a pack file"));
+    }
+    if read_u32(reader)? != VERSION {
+        return Err(invalid("unsupported pack version"));
+    }
+
+    let count = read_u32(reader)?;
+    let mut entries = HashMap::new();
+    for _ in 0..count {
+        let path_len = read_u32(reader)? as u64;
+        if path_len > len.saturating_sub(reader.stream_position()?) {
+            return Err(invalid("pack is cut short"));
+        }
+        let mut path = vec![0; path_len as usize];
+        reader.read_exact(&mut path)?;
+        let path = String::from_utf8(path).map_err(|_| invalid("pack path isn't utf-8"))?;
+        let offset = read_u64(reader)?;
+        let file_len = read_u64(reader)?;
+        entries.insert(path, (offset, file_len));
+    }
+
+    let start = reader.stream_position()?;
+    let data_len = len.saturating_sub(start);
+    for &(offset, file_len) in entries.values() {
+        if !matches!(offset.checked_add(file_len), Some(end) if end <= data_len) {
+            return Err(invalid("pack is cut short"));
+        }
+    }
+    Ok((entries, start))
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Source for Pack {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let Some(&(offset, len)) = self.entries.get(path) else {
+            return Err(io::ErrorKind::NotFound.into());
+        };
+        let (Data::File { start, .. } | Data::Bytes { start, .. }) = &self.data;
+        let Some((start, end)) = start
+            .checked_add(offset)
+            .and_then(|start| Some((start, start.checked_add(len)?)))
+        else {
+            return Err(invalid("pack is cut short"));
+        };
+        match &self.data {
+            Data::File { path, .. } => {
+                // The file may have changed since it was opened, so don't
+                // trust the length enough to allocate it up front
+                let mut file = File::open(path)?;
+                file.seek(SeekFrom::Start(start))?;
+                let mut bytes = Vec::new();
+                file.take(len).read_to_end(&mut bytes)?;
+                if bytes.len() as u64 != len {
+                    return Err(invalid("pack is cut short"));
+                }
+                Ok(bytes)
+            }
+            Data::Bytes { bytes, .. } => usize::try_from(start)
+                .ok()
+                .zip(usize::try_from(end).ok())
+                .and_then(|(start, end)| bytes.get(start..end))
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| invalid("pack is cut short")),
+        }
+    }
+
+    /// When the pack file changed, for files in the pack.
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        match &self.data {
+            Data::File { path: file, .. } if self.entries.contains_key(path) => {
+                fs::metadata(file).and_then(|m| m.modified()).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Makes a [`Pack`], for example from the assets folder in a build script.
+#[derive(Default)]
+pub struct PackBuilder {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl PackBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, replacing any added at the same path.
+    pub fn add(&mut self, path: &str, bytes: impl Into<Vec<u8>>) {
+        let path = normalize(path).expect("path leaves the file system");
+        self.files.insert(path, bytes.into());
+    }
+    /// Add every file in `dir` and its subdirectories, at their paths
+    /// relative to `dir`.
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        self.add_dir_at(dir.as_ref(), "")
+    }
+    fn add_dir_at(&mut self, dir: &Path, prefix: &str) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name
+                .to_str()
+                .ok_or_else(|| invalid("file name isn't utf-8"))?;
+            let path = if prefix.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{prefix}/{name}")
+            };
+            if entry.file_type()?.is_dir() {
+                self.add_dir_at(&entry.path(), &path)?;
+            } else {
+                self.add(&path, fs::read(entry.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.files.len() as u32).to_le_bytes())?;
+        let mut offset = 0u64;
+        for (path, bytes) in &self.files {
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            offset += bytes.len() as u64;
+        }
+        for bytes in self.files.values() {
+            writer.write_all(bytes)?;
+        }
+        Ok(())
+    }
+    /// The pack as bytes, for [`Pack::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes).expect("writing to a vec can't fail");
+        bytes
+    }
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        // Dropping the writer would flush it too, but ignore any error
+        writer.flush()
+    }
+}
@@ -0,0 +1,90 @@
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+use miniquad::EventHandler;
+
+use super::{assets::texture::Texture, Game};
+use crate::{
+    self as mozart,
+    math::{color::Color, transform::Transform},
+    obj::{sprite::Sprite, Make, Obj},
+    Error, Result,
+};
+
+mod font;
+
+/// Where the error that stopped a game is kept. It is shared with
+/// [`GameBuilder::start`](super::GameBuilder::start), which returns it once
+/// the window closes.
+pub(crate) type ErrorSlot = Rc<RefCell<Option<Error>>>;
+
+/// Shown instead of the game in debug builds once it has failed, with the
+/// error written on it.
+#[derive(Obj)]
+struct ErrorScene {
+    message: Sprite,
+}
+
+impl ErrorScene {
+    const MARGIN: f32 = 16.;
+    const TEXT_SCALE: f32 = 2.;
+}
+
+impl Make for ErrorScene {
+    type Config = String;
+
+    fn make(game: &mut Game, message: Self::Config) -> Result<Self> {
+        game.clear_color = Color::from_hex_rgb(0x8b1010);
+
+        let width = (game.window_size().x - 2. * Self::MARGIN) / Self::TEXT_SCALE;
+        let image = font::render(&message, width.max(0.) as u32, Color::WHITE);
+        let texture = Arc::new(Texture {
+            gl_texture: game.gl.create_texture(&image),
+            image,
+        });
+        let transform = Transform::IDENTITY
+            .scaled_uniform(Self::TEXT_SCALE)
+            .with_offset((Self::MARGIN, Self::MARGIN));
+
+        Ok(Self {
+            message: Sprite::from_texture(game, texture, transform),
+        })
+    }
+}
+
+/// Stands in for the game when it couldn't be built at all.
+pub(crate) struct NoGame;
+
+impl EventHandler for NoGame {
+    fn update(&mut self) {}
+    fn draw(&mut self) {}
+}
+
+impl Game {
+    /// Stop the game because of an error. Every scene exits, then debug
+    /// builds show the error on a red screen until the window is closed, and
+    /// other builds quit. The error isn't printed here, it is returned from
+    /// [`GameBuilder::start`](super::GameBuilder::start) or
+    /// [`Game::take_error`].
+    pub(crate) fn fail(&mut self, error: Error) {
+        self.exit_scenes();
+        self.block_quit(false);
+
+        if cfg!(debug_assertions) && !self.window.is_headless() {
+            let message = format!("error: {error:?}");
+            let scene = ErrorScene::make(self, message).expect("error scene can't fail");
+            self.scenes.push(Box::new(scene));
+        } else {
+            self.quit();
+        }
+
+        // Keep the first error, which caused the rest
+        self.error.borrow_mut().get_or_insert(error);
+    }
+
+    /// The error that stopped the game, if there was one. Useful for headless
+    /// games, windowed games return it from
+    /// [`GameBuilder::start`](super::GameBuilder::start).
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+}
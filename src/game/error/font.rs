@@ -0,0 +1,147 @@
+//! A tiny built-in font, so errors can be shown without loading any assets.
+
+use crate::{game::assets::texture::Image, math::color::Color};
+
+/// Width of a glyph in pixels, not counting the gap after it.
+const GLYPH_WIDTH: u32 = 5;
+/// Horizontal space taken by a glyph.
+pub(super) const ADVANCE: u32 = GLYPH_WIDTH + 1;
+/// Vertical space taken by a line of text.
+pub(super) const LINE_HEIGHT: u32 = 10;
+
+/// Glyphs for printable ASCII, starting at the space. Each byte is a column,
+/// left to right, with the top pixel in the lowest bit.
+#[rustfmt::skip]
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5f, 0x00, 0x00], // space !
+    [0x00, 0x07, 0x00, 0x07, 0x00], [0x14, 0x7f, 0x14, 0x7f, 0x14], // " #
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62], // $ %
+    [0x36, 0x49, 0x56, 0x20, 0x50], [0x00, 0x08, 0x07, 0x03, 0x00], // & '
+    [0x00, 0x1c, 0x22, 0x41, 0x00], [0x00, 0x41, 0x22, 0x1c, 0x00], // ( )
+    [0x2a, 0x1c, 0x7f, 0x1c, 0x2a], [0x08, 0x08, 0x3e, 0x08, 0x08], // * +
+    [0x00, 0x80, 0x70, 0x30, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], // , -
+    [0x00, 0x00, 0x60, 0x60, 0x00], [0x20, 0x10, 0x08, 0x04, 0x02], // . /
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], [0x00, 0x42, 0x7f, 0x40, 0x00], // 0 1
+    [0x72, 0x49, 0x49, 0x49, 0x46], [0x21, 0x41, 0x49, 0x4d, 0x33], // 2 3
+    [0x18, 0x14, 0x12, 0x7f, 0x10], [0x27, 0x45, 0x45, 0x45, 0x39], // 4 5
+    [0x3c, 0x4a, 0x49, 0x49, 0x31], [0x41, 0x21, 0x11, 0x09, 0x07], // 6 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x46, 0x49, 0x49, 0x29, 0x1e], // 8 9
+    [0x00, 0x00, 0x14, 0x00, 0x00], [0x00, 0x40, 0x34, 0x00, 0x00], // : ;
+    [0x00, 0x08, 0x14, 0x22, 0x41], [0x14, 0x14, 0x14, 0x14, 0x14], // < =
+    [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x59, 0x09, 0x06], // > ?
+    [0x3e, 0x41, 0x5d, 0x59, 0x4e], [0x7c, 0x12, 0x11, 0x12, 0x7c], // @ A
+    [0x7f, 0x49, 0x49, 0x49, 0x36], [0x3e, 0x41, 0x41, 0x41, 0x22], // B C
+    [0x7f, 0x41, 0x41, 0x41, 0x3e], [0x7f, 0x49, 0x49, 0x49, 0x41], // D E
+    [0x7f, 0x09, 0x09, 0x09, 0x01], [0x3e, 0x41, 0x41, 0x51, 0x73], // F G
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], [0x00, 0x41, 0x7f, 0x41, 0x00], // H I
+    [0x20, 0x40, 0x41, 0x3f, 0x01], [0x7f, 0x08, 0x14, 0x22, 0x41], // J K
+    [0x7f, 0x40, 0x40, 0x40, 0x40], [0x7f, 0x02, 0x1c, 0x02, 0x7f], // L M
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], [0x3e, 0x41, 0x41, 0x41, 0x3e], // N O
+    [0x7f, 0x09, 0x09, 0x09, 0x06], [0x3e, 0x41, 0x51, 0x21, 0x5e], // P Q
+    [0x7f, 0x09, 0x19, 0x29, 0x46], [0x26, 0x49, 0x49, 0x49, 0x32], // R S
+    [0x03, 0x01, 0x7f, 0x01, 0x03], [0x3f, 0x40, 0x40, 0x40, 0x3f], // T U
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], [0x3f, 0x40, 0x38, 0x40, 0x3f], // V W
+    [0x63, 0x14, 0x08, 0x14, 0x63], [0x03, 0x04, 0x78, 0x04, 0x03], // X Y
+    [0x61, 0x59, 0x49, 0x4d, 0x43], [0x00, 0x7f, 0x41, 0x41, 0x41], // Z [
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x41, 0x7f], // \ ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], [0x40, 0x40, 0x40, 0x40, 0x40], // ^ _
+    [0x00, 0x03, 0x07, 0x08, 0x00], [0x20, 0x54, 0x54, 0x78, 0x40], // ` a
+    [0x7f, 0x28, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x28], // b c
+    [0x38, 0x44, 0x44, 0x28, 0x7f], [0x38, 0x54, 0x54, 0x54, 0x18], // d e
+    [0x00, 0x08, 0x7e, 0x09, 0x02], [0x18, 0xa4, 0xa4, 0x9c, 0x78], // f g
+    [0x7f, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7d, 0x40, 0x00], // h i
+    [0x20, 0x40, 0x40, 0x3d, 0x00], [0x7f, 0x10, 0x28, 0x44, 0x00], // j k
+    [0x00, 0x41, 0x7f, 0x40, 0x00], [0x7c, 0x04, 0x78, 0x04, 0x78], // l m
+    [0x7c, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38], // n o
+    [0xfc, 0x18, 0x24, 0x24, 0x18], [0x18, 0x24, 0x24, 0x18, 0xfc], // p q
+    [0x7c, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x24], // r s
+    [0x04, 0x04, 0x3f, 0x44, 0x24], [0x3c, 0x40, 0x40, 0x20, 0x7c], // t u
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], [0x3c, 0x40, 0x30, 0x40, 0x3c], // v w
+    [0x44, 0x28, 0x10, 0x28, 0x44], [0x4c, 0x90, 0x90, 0x90, 0x7c], // x y
+    [0x44, 0x64, 0x54, 0x4c, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00], // z {
+    [0x00, 0x00, 0x77, 0x00, 0x00], [0x00, 0x41, 0x36, 0x08, 0x00], // | }
+    [0x02, 0x01, 0x02, 0x04, 0x02],                                 // ~
+];
+
+/// Columns of the glyph for `c`. Characters the font lacks show as `?`.
+fn glyph(c: char) -> [u8; 5] {
+    let index = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    GLYPHS[index]
+}
+
+/// Break `text` into lines of at most `columns` characters, at spaces where
+/// possible.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let columns = columns.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.chars().count() > columns {
+                lines.push(std::mem::take(&mut line));
+            } else if line_len > 0 {
+                line.push(' ');
+            }
+            for c in word.chars() {
+                if line.chars().count() == columns {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Draw `text` in `color` on a transparent image, wrapped to fit
+/// `max_width` pixels.
+pub(super) fn render(text: &str, max_width: u32, color: Color) -> Image {
+    let lines = wrap(text, (max_width / ADVANCE) as usize);
+    let columns = lines.iter().map(|line| line.chars().count()).max();
+
+    let width = (columns.unwrap_or(0) as u32 * ADVANCE).max(1);
+    let height = (lines.len() as u32 * LINE_HEIGHT).max(1);
+    let mut image = Image::from_color(width, height, Color::TRANSPARENT);
+
+    let color: [u8; 4] = color.into();
+    for (row, line) in lines.iter().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            let (left, top) = (column as u32 * ADVANCE, row as u32 * LINE_HEIGHT);
+            for (x, bits) in glyph(c).into_iter().enumerate() {
+                for y in (0..8).filter(|y| bits & 1 << y != 0) {
+                    let pixel = ((top + y) * width + left + x as u32) as usize * 4;
+                    image.bytes[pixel..pixel + 4].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_at_spaces_and_splits_long_words() {
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap("abcdefgh", 3), ["abc", "def", "gh"]);
+        assert_eq!(wrap("a\n\nb", 10), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn renders_glyph_pixels() {
+        let image = render("I", 100, Color::WHITE);
+        assert_eq!((image.width, image.height), (ADVANCE, LINE_HEIGHT));
+
+        let lit = |x: u32, y: u32| image.bytes[((y * image.width + x) * 4 + 3) as usize] != 0;
+        // The middle column of I is solid, its corners are empty
+        assert!((0..7).all(|y| lit(2, y)));
+        assert!(!lit(0, 3) && !lit(4, 3));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+use super::Game;
+
+trait AnyQueue: Any {
+    /// Make pending events readable and drop the ones that were.
+    fn swap(&mut self);
+}
+
+struct Queue<T> {
+    readable: Vec<T>,
+    pending: Vec<T>,
+}
+
+impl<T: 'static> AnyQueue for Queue<T> {
+    fn swap(&mut self) {
+        self.readable.clear();
+        std::mem::swap(&mut self.readable, &mut self.pending);
+    }
+}
+
+/// Typed event queues, one per event type.
+///
+/// Events emitted during a frame are readable for the whole next frame, from
+/// before servers' [`pre_update`](super::server::Server::pre_update) until
+/// after their [`post_update`](super::server::Server::post_update). This way
+/// every object sees every event exactly once, no matter where it is in the
+/// tree.
+pub(crate) struct Events {
+    queues: HashMap<TypeId, Box<dyn AnyQueue>>,
+}
+
+impl Events {
+    pub(crate) fn new() -> Self {
+        Self {
+            queues: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn swap(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.swap();
+        }
+    }
+}
+
+impl Game {
+    /// Send an event to every object. It can be read with [`Game::events`]
+    /// during the next frame.
+    pub fn emit<T: 'static>(&mut self, event: T) {
+        let queue = self
+            .events
+            .queues
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| {
+                Box::new(Queue::<T> {
+                    readable: Vec::new(),
+                    pending: Vec::new(),
+                })
+            });
+        let queue: &mut dyn Any = queue.as_mut();
+        queue
+            .downcast_mut::<Queue<T>>()
+            .expect("valid queue in event bus")
+            .pending
+            .push(event);
+    }
+
+    /// Every `T` emitted last frame, in the order they were emitted.
+    ///
+    /// Events are read one frame after they are emitted, even by objects
+    /// that update after the emitter in the same frame. That way every object
+    /// sees each event exactly once, wherever it is in the tree. Events the
+    /// game emits between frames, like
+    /// [`QuitRequested`](super::window::QuitRequested), are readable in the
+    /// next update.
+    pub fn events<T: 'static>(&self) -> &[T] {
+        let Some(queue) = self.events.queues.get(&TypeId::of::<T>()) else {
+            return &[];
+        };
+        let queue: &dyn Any = queue.as_ref();
+        &queue
+            .downcast_ref::<Queue<T>>()
+            .expect("valid queue in event bus")
+            .readable
+    }
+}
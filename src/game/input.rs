@@ -1,37 +1,302 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, hash::Hash};
 
-pub use miniquad::{KeyCode, MouseButton};
+pub use action::{Actions, Binding};
+pub use drop::DroppedFile;
+pub use event::InputEvent;
+pub use gamepad::{
+    GamepadAxis, GamepadBackend, GamepadButton, GamepadEvent, Rumble, VirtualGamepads,
+};
+pub use gesture::Gesture;
+pub use miniquad::{KeyCode, KeyMods, MouseButton};
+pub use record::{RecordedFrame, Recording};
+pub use script::InputScript;
+pub use touch::{Touch, TouchPhase};
 
+use super::Game;
+use crate::math::point::{pt2, Pt2};
+
+pub mod action;
+mod drop;
+mod event;
+pub mod gamepad;
+pub mod gesture;
+mod keys;
+pub mod record;
+pub mod script;
+pub mod touch;
+
+/// Keys or mouse buttons that are held down, with the ones pressed and
+/// released this frame.
+struct Buttons<T> {
+    down: HashSet<T>,
+    /// presses and releases since the last frame, in the order they happened
+    pending: Vec<(T, bool)>,
+
+    pressed: HashSet<T>,
+    released: HashSet<T>,
+    /// edges not yet seen by a fixed update. there can be several fixed
+    /// updates in a frame, or none.
+    fixed_pressed: HashSet<T>,
+    fixed_released: HashSet<T>,
+}
+
+impl<T: Eq + Hash + Copy> Buttons<T> {
+    fn new() -> Self {
+        Self {
+            down: HashSet::new(),
+            pending: Vec::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+            fixed_pressed: HashSet::new(),
+            fixed_released: HashSet::new(),
+        }
+    }
+
+    fn set(&mut self, button: T, down: bool) {
+        self.pending.push((button, down));
+    }
+    fn release_all(&mut self) {
+        self.release_where(|_| true);
+    }
+    /// Release the held buttons `filter` returns `true` for, including ones
+    /// pressed since the last frame.
+    fn release_where(&mut self, filter: impl Fn(&T) -> bool) {
+        let held: Vec<T> = self
+            .down
+            .iter()
+            .copied()
+            .chain(
+                self.pending
+                    .iter()
+                    .filter(|(_, down)| *down)
+                    .map(|(b, _)| *b),
+            )
+            .filter(filter)
+            .collect();
+        for button in held {
+            self.set(button, false);
+        }
+    }
+
+    fn begin_frame(&mut self, fixed_timestep: bool) {
+        self.pressed.clear();
+        self.released.clear();
+        for (button, down) in self.pending.drain(..) {
+            if down {
+                if self.down.insert(button) {
+                    self.pressed.insert(button);
+                }
+            } else if self.down.remove(&button) {
+                self.released.insert(button);
+            }
+        }
+
+        if fixed_timestep {
+            self.fixed_pressed.extend(&self.pressed);
+            self.fixed_released.extend(&self.released);
+        }
+    }
+    fn end_fixed_step(&mut self) {
+        self.fixed_pressed.clear();
+        self.fixed_released.clear();
+    }
+
+    fn pressed(&self, fixed: bool) -> &HashSet<T> {
+        if fixed {
+            &self.fixed_pressed
+        } else {
+            &self.pressed
+        }
+    }
+    fn released(&self, fixed: bool) -> &HashSet<T> {
+        if fixed {
+            &self.fixed_released
+        } else {
+            &self.released
+        }
+    }
+    fn is_pressed(&self, button: T, fixed: bool) -> bool {
+        self.pressed(fixed).contains(&button)
+    }
+    fn is_released(&self, button: T, fixed: bool) -> bool {
+        self.released(fixed).contains(&button)
+    }
+}
+
+/// State of the keyboard, mouse, touches and gamepads. Events are queued as they arrive and
+/// applied all at once at the start of each frame, so the state doesn't
+/// change during an update.
+///
+/// A key or button is pressed or released for exactly one update after it
+/// happens, and for exactly one fixed update when using a fixed timestep,
+/// even if that fixed update only runs a few frames later.
 pub struct Input {
-    keys_down: HashSet<KeyCode>,
-    mouse_buttons_down: HashSet<MouseButton>,
+    keys: Buttons<KeyCode>,
+    mouse_buttons: Buttons<MouseButton>,
+    mouse_position: Pt2,
+    mouse_world_position: Pt2,
+    /// wheel movement this frame
+    mouse_wheel: Pt2,
+    pending_mouse_wheel: Pt2,
+    modifiers: KeyMods,
+    pending_modifiers: Option<KeyMods>,
+    /// characters typed this frame
+    text: String,
+    pending_text: String,
+    touches: touch::Touches,
+    gamepads: gamepad::Gamepads,
+    /// files dropped this frame
+    dropped_files: Vec<DroppedFile>,
+    pending_dropped_files: Vec<DroppedFile>,
+    /// whether a fixed update is running
+    fixed: bool,
+
+    pub actions: Actions,
 }
 
 impl Input {
     pub(crate) fn new() -> Self {
         Self {
-            keys_down: HashSet::new(),
-            mouse_buttons_down: HashSet::new(),
+            keys: Buttons::new(),
+            mouse_buttons: Buttons::new(),
+            mouse_position: Pt2::ZERO,
+            mouse_world_position: Pt2::ZERO,
+            mouse_wheel: Pt2::ZERO,
+            pending_mouse_wheel: Pt2::ZERO,
+            modifiers: KeyMods::default(),
+            pending_modifiers: None,
+            text: String::new(),
+            pending_text: String::new(),
+            touches: touch::Touches::new(),
+            gamepads: gamepad::Gamepads::new(),
+            dropped_files: Vec::new(),
+            pending_dropped_files: Vec::new(),
+            fixed: false,
+            actions: Actions::new(),
         }
     }
 
     pub(crate) fn set_key_down(&mut self, key: KeyCode) {
-        self.keys_down.insert(key);
+        self.keys.set(key, true);
     }
     pub(crate) fn set_key_up(&mut self, key: KeyCode) {
-        self.keys_down.remove(&key);
+        self.keys.set(key, false);
     }
     pub(crate) fn set_mb_down(&mut self, button: MouseButton) {
-        self.mouse_buttons_down.insert(button);
+        self.mouse_buttons.set(button, true);
     }
     pub(crate) fn set_mb_up(&mut self, button: MouseButton) {
-        self.mouse_buttons_down.remove(&button);
+        self.mouse_buttons.set(button, false);
+    }
+    pub(crate) fn add_mouse_wheel(&mut self, delta: Pt2) {
+        self.pending_mouse_wheel += delta;
+    }
+    pub(crate) fn set_modifiers(&mut self, modifiers: KeyMods) {
+        self.pending_modifiers = Some(modifiers);
+    }
+    pub(crate) fn add_char(&mut self, character: char) {
+        // Keys like backspace and enter are reported as characters too
+        if !character.is_control() {
+            self.pending_text.push(character);
+        }
+    }
+    /// Release every key and button, when their release won't be reported,
+    /// like after the window loses focus.
+    pub(crate) fn release_all(&mut self) {
+        self.keys.release_all();
+        self.mouse_buttons.release_all();
+        self.pending_modifiers = Some(KeyMods::default());
+    }
+
+    /// Apply the events that arrived since the last frame. `now` is the
+    /// unscaled time, used to recognize gestures.
+    pub(crate) fn begin_frame(&mut self, now: f64, fixed_timestep: bool) {
+        self.keys.begin_frame(fixed_timestep);
+        self.mouse_buttons.begin_frame(fixed_timestep);
+        self.mouse_wheel = std::mem::replace(&mut self.pending_mouse_wheel, Pt2::ZERO);
+        if let Some(modifiers) = self.pending_modifiers.take() {
+            self.modifiers = modifiers;
+        }
+        self.text.clear();
+        std::mem::swap(&mut self.text, &mut self.pending_text);
+        self.touches.begin_frame(now);
+        self.dropped_files.clear();
+        std::mem::swap(&mut self.dropped_files, &mut self.pending_dropped_files);
+        self.gamepads
+            .begin_frame(self.actions.deadzone(), fixed_timestep);
+    }
+    pub(crate) fn begin_fixed_step(&mut self) {
+        self.fixed = true;
+    }
+    pub(crate) fn end_fixed_step(&mut self) {
+        self.fixed = false;
+        self.keys.end_fixed_step();
+        self.mouse_buttons.end_fixed_step();
+        self.gamepads.end_fixed_step();
     }
 
     pub fn is_key_down(&self, key: KeyCode) -> bool {
-        self.keys_down.contains(&key)
+        self.keys.down.contains(&key)
     }
+    /// Whether the key was pressed since the last update.
+    pub fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.keys.is_pressed(key, self.fixed)
+    }
+    /// Whether the key was released since the last update.
+    pub fn is_key_released(&self, key: KeyCode) -> bool {
+        self.keys.is_released(key, self.fixed)
+    }
+
+    /// Modifier keys held at the start of the frame, for shortcuts like
+    /// Ctrl+S.
+    pub fn modifiers(&self) -> KeyMods {
+        self.modifiers
+    }
+    /// Text typed since the last update, in the order it was typed. Includes
+    /// characters from key repeat and input methods, but not control
+    /// characters like backspace.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
     pub fn is_mouse_down(&self, button: MouseButton) -> bool {
-        self.mouse_buttons_down.contains(&button)
+        self.mouse_buttons.down.contains(&button)
+    }
+    /// Whether the button was pressed since the last update.
+    pub fn is_mouse_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.is_pressed(button, self.fixed)
+    }
+    /// Whether the button was released since the last update.
+    pub fn is_mouse_released(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.is_released(button, self.fixed)
+    }
+
+    /// Position of the mouse in pixels from the top left of the window.
+    pub fn mouse_position(&self) -> Pt2 {
+        self.mouse_position
+    }
+    /// Position of the mouse in the same coordinates objects are drawn in.
+    pub fn mouse_world_position(&self) -> Pt2 {
+        self.mouse_world_position
+    }
+    /// How far the mouse wheel moved this frame. `y` is positive when
+    /// scrolling up, and `x` when scrolling right.
+    pub fn mouse_wheel(&self) -> Pt2 {
+        self.mouse_wheel
+    }
+}
+
+impl Game {
+    /// Move the mouse to `position` in window pixels.
+    pub(crate) fn set_mouse_position(&mut self, position: Pt2) {
+        let size = self.window_size;
+        let clip = pt2(position.x / size.x * 2. - 1., 1. - position.y / size.y * 2.);
+        let world = match self.gl.viewport_transform().inverse() {
+            Some(transform) => clip * transform,
+            None => position,
+        };
+
+        self.input.mouse_position = position;
+        self.input.mouse_world_position = world;
     }
 }
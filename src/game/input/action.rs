@@ -0,0 +1,307 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
+
+use super::{keys, GamepadAxis, GamepadButton, Input, KeyCode, MouseButton};
+use crate::{
+    math::point::{pt2, Pt2},
+    Error, Result,
+};
+
+/// An input that an action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// A button on any gamepad.
+    Gamepad(GamepadButton),
+    /// An axis on any gamepad, pushed in one direction. Held once it is past
+    /// the deadzone.
+    Axis {
+        axis: GamepadAxis,
+        positive: bool,
+    },
+}
+
+impl From<KeyCode> for Binding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+impl From<MouseButton> for Binding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+impl From<GamepadButton> for Binding {
+    fn from(button: GamepadButton) -> Self {
+        Self::Gamepad(button)
+    }
+}
+
+/// Written as `key:Space`, `mouse:Left`, `pad:South` or `axis:LeftX-`, the
+/// way bindings are saved.
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "key:{}", keys::key_name(*key)),
+            Binding::Mouse(button) => write!(f, "mouse:{}", keys::mouse_button_name(*button)),
+            Binding::Gamepad(button) => write!(f, "pad:{button}"),
+            Binding::Axis { axis, positive } => {
+                write!(f, "axis:{axis}{}", if *positive { '+' } else { '-' })
+            }
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, name) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected `kind:name`, found `{s}`"))?;
+        match kind {
+            "key" => keys::key_from_name(name)
+                .map(Binding::Key)
+                .ok_or_else(|| format!("unknown key `{name}`")),
+            "mouse" => keys::mouse_button_from_name(name)
+                .map(Binding::Mouse)
+                .ok_or_else(|| format!("unknown mouse button `{name}`")),
+            "pad" => name.parse().map(Binding::Gamepad),
+            "axis" => {
+                let (axis, positive) = if let Some(axis) = name.strip_suffix('+') {
+                    (axis, true)
+                } else if let Some(axis) = name.strip_suffix('-') {
+                    (axis, false)
+                } else {
+                    return Err(format!(
+                        "expected `+` or `-` after the axis, found `{name}`"
+                    ));
+                };
+                Ok(Binding::Axis {
+                    axis: axis.parse()?,
+                    positive,
+                })
+            }
+            _ => Err(format!("unknown kind of input `{kind}`")),
+        }
+    }
+}
+
+/// Named actions, each bound to any number of inputs, so gameplay code can ask
+/// about `"jump"` instead of a specific key. Bindings can be changed while the
+/// game runs, and saved to a file for the player to keep.
+///
+/// Saved bindings have one action per line, like `jump = key:Space, mouse:Left`.
+/// Empty lines and lines starting with `#` are skipped.
+pub struct Actions {
+    bindings: BTreeMap<String, Vec<Binding>>,
+    deadzone: f32,
+}
+
+impl Actions {
+    pub(crate) fn new() -> Self {
+        Self {
+            bindings: BTreeMap::new(),
+            deadzone: 0.2,
+        }
+    }
+
+    /// Bind `action` to another input.
+    pub fn bind(&mut self, action: &str, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action.to_owned()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+    pub fn unbind(&mut self, action: &str, binding: impl Into<Binding>) {
+        let binding = binding.into();
+        if let Some(bindings) = self.bindings.get_mut(action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+    /// Replace one of the inputs `action` is bound to, keeping its place.
+    /// Binds `new` if `action` wasn't bound to `old`.
+    pub fn rebind(&mut self, action: &str, old: impl Into<Binding>, new: impl Into<Binding>) {
+        let (old, new) = (old.into(), new.into());
+        let bindings = self.bindings.entry(action.to_owned()).or_default();
+        bindings.retain(|b| *b != new);
+        match bindings.iter_mut().find(|b| **b == old) {
+            Some(binding) => *binding = new,
+            None => bindings.push(new),
+        }
+    }
+    /// Remove every binding of `action`.
+    pub fn clear(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+    /// Names of every action with bindings, in alphabetical order.
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.bindings.keys().map(String::as_str)
+    }
+
+    /// How far an axis has to move before [`Input::get_axis`] and
+    /// [`Input::get_vector`] stop returning zero. Defaults to 0.2.
+    pub fn deadzone(&self) -> f32 {
+        self.deadzone
+    }
+    pub fn set_deadzone(&mut self, deadzone: f32) {
+        assert!((0. ..1.).contains(&deadzone), "deadzone must be in 0..1");
+        self.deadzone = deadzone;
+    }
+
+    /// Write every binding in the saved format.
+    pub fn save(&self) -> String {
+        let mut text = String::new();
+        for (action, bindings) in &self.bindings {
+            let bindings: Vec<String> = bindings.iter().map(Binding::to_string).collect();
+            text += &format!("{action} = {}\n", bindings.join(", "));
+        }
+        text
+    }
+    /// Replace every binding with the ones in `text`. Nothing changes if it
+    /// isn't valid.
+    pub fn load(&mut self, text: &str) -> Result<()> {
+        let mut loaded = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message| Error::Parse {
+                what: "input bindings",
+                line: i + 1,
+                message,
+            };
+
+            let (action, bindings) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `action = bindings`".to_owned()))?;
+            let bindings = bindings
+                .split(',')
+                .map(str::trim)
+                .filter(|b| !b.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<Binding>, _>>()
+                .map_err(error)?;
+            loaded.insert(action.trim().to_owned(), bindings);
+        }
+
+        self.bindings = loaded;
+        Ok(())
+    }
+
+    pub fn save_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.save()).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        self.load(&text)
+    }
+}
+
+impl Input {
+    fn is_binding_down(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_down(key),
+            Binding::Mouse(button) => self.is_mouse_down(button),
+            Binding::Gamepad(button) => self.is_any_gamepad_down(button),
+            Binding::Axis { axis, positive } => self.is_any_axis_down(axis, positive),
+        }
+    }
+    fn is_binding_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_pressed(key),
+            Binding::Mouse(button) => self.is_mouse_pressed(button),
+            Binding::Gamepad(button) => self.is_any_gamepad_pressed(button),
+            Binding::Axis { axis, positive } => self.is_any_axis_pressed(axis, positive),
+        }
+    }
+    fn is_binding_released(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.is_key_released(key),
+            Binding::Mouse(button) => self.is_mouse_released(button),
+            Binding::Gamepad(button) => self.is_any_gamepad_released(button),
+            Binding::Axis { axis, positive } => self.is_any_axis_released(axis, positive),
+        }
+    }
+
+    pub fn is_action_down(&self, action: &str) -> bool {
+        let bindings = self.actions.bindings(action);
+        bindings.iter().any(|&b| self.is_binding_down(b))
+    }
+    /// Whether the action started being held since the last update. Pressing a
+    /// second input bound to an action that is already held doesn't count.
+    pub fn is_action_pressed(&self, action: &str) -> bool {
+        let bindings = self.actions.bindings(action);
+        bindings.iter().any(|&b| self.is_binding_pressed(b))
+            && !bindings
+                .iter()
+                .any(|&b| self.is_binding_down(b) && !self.is_binding_pressed(b))
+    }
+    /// Whether the action stopped being held since the last update.
+    pub fn is_action_released(&self, action: &str) -> bool {
+        let bindings = self.actions.bindings(action);
+        bindings.iter().any(|&b| self.is_binding_released(b)) && !self.is_action_down(action)
+    }
+    /// How strongly the action is held, from 0 to 1. Buttons are either 0 or
+    /// 1, axes can be anything in between.
+    pub fn action_strength(&self, action: &str) -> f32 {
+        let strength = |&binding| match binding {
+            Binding::Axis { axis, positive } => self.any_gamepad_axis(axis, positive),
+            binding if self.is_binding_down(binding) => 1.,
+            _ => 0.,
+        };
+        let bindings = self.actions.bindings(action);
+        bindings.iter().map(strength).fold(0., f32::max)
+    }
+
+    /// A value from -1 to 1, from how strongly `negative` and `positive` are
+    /// held.
+    pub fn get_axis(&self, negative: &str, positive: &str) -> f32 {
+        let value = self.action_strength(positive) - self.action_strength(negative);
+        let deadzone = self.actions.deadzone;
+        if value.abs() <= deadzone {
+            return 0.;
+        }
+        value.signum() * (value.abs() - deadzone) / (1. - deadzone)
+    }
+    /// A direction no longer than 1, from four actions. `y` points down, like
+    /// the window.
+    pub fn get_vector(&self, left: &str, right: &str, up: &str, down: &str) -> Pt2 {
+        let value = pt2(
+            self.action_strength(right) - self.action_strength(left),
+            self.action_strength(down) - self.action_strength(up),
+        );
+        let length = value.length();
+        let deadzone = self.actions.deadzone;
+        if length <= deadzone {
+            return Pt2::ZERO;
+        }
+        let scaled = ((length - deadzone) / (1. - deadzone)).min(1.);
+        value * (scaled / length)
+    }
+
+    /// A key, mouse button or gamepad input pressed since the last update, if
+    /// there is one. Useful for letting the player pick a new binding.
+    pub fn pressed_binding(&self) -> Option<Binding> {
+        let keys = self.keys.pressed(self.fixed).iter();
+        let buttons = self.mouse_buttons.pressed(self.fixed).iter();
+        keys.map(|&key| Binding::Key(key))
+            .chain(buttons.map(|&button| Binding::Mouse(button)))
+            .next()
+            .or_else(|| self.pressed_gamepad_binding())
+    }
+}
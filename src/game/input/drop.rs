@@ -0,0 +1,89 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use super::Input;
+use crate::{
+    game::{
+        assets::{self, Asset, AssetError, AssetErrorKind, GlAsset},
+        Game,
+    },
+    Error, Result,
+};
+
+/// A file dropped onto the window.
+#[derive(Debug, Clone)]
+pub struct DroppedFile {
+    /// Where the file was dropped from. On the web this is only the file's
+    /// name.
+    pub path: PathBuf,
+    pub bytes: Arc<[u8]>,
+}
+
+impl DroppedFile {
+    /// Extension of the file, like `png`, to pick how to load it.
+    pub fn extension(&self) -> Option<&str> {
+        self.path.extension()?.to_str()
+    }
+
+    fn error<T>(&self, kind: AssetErrorKind) -> AssetError {
+        assets::error::<T>(self.path.display().to_string(), kind)
+    }
+}
+
+impl Input {
+    /// Files dropped onto the window since the last update, in the order they
+    /// were dropped. Dropped files aren't kept in
+    /// [`Recording`](super::Recording)s.
+    pub fn dropped_files(&self) -> &[DroppedFile] {
+        &self.dropped_files
+    }
+}
+
+impl Game {
+    /// Read the file at `path` and report it as dropped onto the window. It
+    /// shows up in [`Input::dropped_files`] from the next frame.
+    pub fn drop_file(&mut self, path: impl Into<PathBuf>) -> Result<()> {
+        let path = path.into();
+        let bytes = fs::read(&path).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        self.input.pending_dropped_files.push(DroppedFile {
+            path,
+            bytes: bytes.into(),
+        });
+        Ok(())
+    }
+
+    /// Load an asset from a dropped file. It isn't cached, since dropped files
+    /// can change between drops.
+    pub fn load_dropped_asset<T: Asset>(&mut self, file: &DroppedFile) -> Result<Arc<T>> {
+        let asset =
+            T::load(&file.bytes).map_err(|err| file.error::<T>(AssetErrorKind::Decode(err)))?;
+        Ok(Arc::new(asset))
+    }
+    /// Load an asset from a dropped file to the GPU, like
+    /// [`Game::load_gl_asset`].
+    #[expect(private_bounds)]
+    pub fn load_dropped_gl_asset<T: GlAsset>(&mut self, file: &DroppedFile) -> Result<Arc<T>> {
+        let data =
+            T::decode(&file.bytes).map_err(|err| file.error::<T>(AssetErrorKind::Decode(err)))?;
+        let asset = T::upload(data, &mut self.gl)
+            .map_err(|err| file.error::<T>(AssetErrorKind::Upload(err)))?;
+        Ok(Arc::new(asset))
+    }
+
+    pub(crate) fn add_window_dropped_files(&mut self) {
+        if self.is_playing_recording() {
+            return;
+        }
+        for i in 0..miniquad::window::dropped_file_count() {
+            let Some(bytes) = miniquad::window::dropped_file_bytes(i) else {
+                continue;
+            };
+            self.input.pending_dropped_files.push(DroppedFile {
+                path: miniquad::window::dropped_file_path(i).unwrap_or_default(),
+                bytes: bytes.into(),
+            });
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::{fmt, str::FromStr};
+
+use super::{keys, GamepadEvent, KeyCode, KeyMods, MouseButton, TouchPhase};
+use crate::{
+    game::Game,
+    math::point::{pt2, Pt2},
+};
+
+/// Something the player did, as reported by the window. Real events and ones
+/// passed to [`Game::inject`] go through the same queue. Positions are in
+/// window pixels.
+///
+/// Events are written one per line in [`InputScript`](super::script::InputScript)s.
+/// Typed spaces and control characters, like the `\r` sent for Enter, are
+/// written as code points:
+///
+/// ```text
+/// key_down Space
+/// key_down S ctrl shift
+/// key_up Space
+/// char a
+/// char U+000D
+/// mouse_move 100 200
+/// mouse_down Left 100 200
+/// mouse_up Left 100 200
+/// wheel 0 -1
+/// touch Started 1 100 200
+/// gamepad 0 connected
+/// gamepad 0 down South
+/// gamepad 0 up South
+/// gamepad 0 axis LeftX -0.5
+/// gamepad 0 disconnected
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    KeyDown {
+        key: KeyCode,
+        modifiers: KeyMods,
+    },
+    KeyUp {
+        key: KeyCode,
+        modifiers: KeyMods,
+    },
+    /// A character was typed.
+    Char(char),
+    MouseMove(Pt2),
+    MouseDown {
+        button: MouseButton,
+        position: Pt2,
+    },
+    MouseUp {
+        button: MouseButton,
+        position: Pt2,
+    },
+    MouseWheel(Pt2),
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        position: Pt2,
+    },
+    Gamepad {
+        pad: usize,
+        event: GamepadEvent,
+    },
+}
+
+impl Game {
+    /// Report an input event, as if it came from the window. It shows up in
+    /// [`Input`](super::Input) from the next frame. Useful for tests and bots
+    /// driving headless games.
+    pub fn inject(&mut self, event: InputEvent) {
+        self.record_input(event);
+        match event {
+            InputEvent::KeyDown { key, modifiers } => {
+                self.input.set_modifiers(modifiers);
+                self.input.set_key_down(key);
+            }
+            InputEvent::KeyUp { key, modifiers } => {
+                self.input.set_modifiers(modifiers);
+                self.input.set_key_up(key);
+            }
+            InputEvent::Char(character) => self.input.add_char(character),
+            InputEvent::MouseMove(position) => self.set_mouse_position(position),
+            InputEvent::MouseDown { button, position } => {
+                self.set_mouse_position(position);
+                self.input.set_mb_down(button);
+            }
+            InputEvent::MouseUp { button, position } => {
+                self.set_mouse_position(position);
+                self.input.set_mb_up(button);
+            }
+            InputEvent::MouseWheel(delta) => self.input.add_mouse_wheel(delta),
+            InputEvent::Touch {
+                id,
+                phase,
+                position,
+            } => self.add_touch(id, phase, position),
+            InputEvent::Gamepad { pad, event } => self.input.gamepads.add_event(pad, event),
+        }
+    }
+}
+
+impl fmt::Display for InputEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = |f: &mut fmt::Formatter<'_>, modifiers: &KeyMods| {
+            let names = [
+                (modifiers.shift, "shift"),
+                (modifiers.ctrl, "ctrl"),
+                (modifiers.alt, "alt"),
+                (modifiers.logo, "logo"),
+            ];
+            for (_, name) in names.iter().filter(|(held, _)| *held) {
+                write!(f, " {name}")?;
+            }
+            Ok(())
+        };
+
+        match self {
+            InputEvent::KeyDown { key, modifiers: m } => {
+                write!(f, "key_down {}", keys::key_name(*key))?;
+                modifiers(f, m)
+            }
+            InputEvent::KeyUp { key, modifiers: m } => {
+                write!(f, "key_up {}", keys::key_name(*key))?;
+                modifiers(f, m)
+            }
+            InputEvent::Char(character) if character.is_control() || character.is_whitespace() => {
+                write!(f, "char U+{:04X}", *character as u32)
+            }
+            InputEvent::Char(character) => write!(f, "char {character}"),
+            InputEvent::MouseMove(p) => write!(f, "mouse_move {} {}", p.x, p.y),
+            InputEvent::MouseDown {
+                button,
+                position: p,
+            } => {
+                write!(
+                    f,
+                    "mouse_down {} {} {}",
+                    keys::mouse_button_name(*button),
+                    p.x,
+                    p.y
+                )
+            }
+            InputEvent::MouseUp {
+                button,
+                position: p,
+            } => {
+                write!(
+                    f,
+                    "mouse_up {} {} {}",
+                    keys::mouse_button_name(*button),
+                    p.x,
+                    p.y
+                )
+            }
+            InputEvent::MouseWheel(d) => write!(f, "wheel {} {}", d.x, d.y),
+            InputEvent::Touch {
+                id,
+                phase,
+                position: p,
+            } => write!(f, "touch {phase:?} {id} {} {}", p.x, p.y),
+            InputEvent::Gamepad { pad, event } => {
+                write!(f, "gamepad {pad} ")?;
+                match event {
+                    GamepadEvent::Connected => write!(f, "connected"),
+                    GamepadEvent::Disconnected => write!(f, "disconnected"),
+                    GamepadEvent::ButtonDown(button) => write!(f, "down {button}"),
+                    GamepadEvent::ButtonUp(button) => write!(f, "up {button}"),
+                    GamepadEvent::Axis(axis, value) => write!(f, "axis {axis} {value}"),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for InputEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The typed character can be a space, so it isn't split
+        if let Some(rest) = s.strip_prefix("char ") {
+            let mut chars = rest.chars();
+            let character = match (chars.next(), chars.next()) {
+                (Some(character), None) => Some(character),
+                _ => rest
+                    .strip_prefix("U+")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32),
+            };
+            return character.map(InputEvent::Char).ok_or_else(|| {
+                format!("expected a character or a code point like U+000D, found `{rest}`")
+            });
+        }
+
+        let mut words = s.split_whitespace();
+        let kind = words.next().ok_or("expected an event")?;
+        let mut next = |what: &str| {
+            words
+                .next()
+                .ok_or_else(|| format!("expected {what} after `{kind}`"))
+        };
+        fn number<T: FromStr>(word: &str) -> Result<T, String> {
+            word.parse()
+                .map_err(|_| format!("expected a number, found `{word}`"))
+        }
+        fn key(word: &str) -> Result<KeyCode, String> {
+            keys::key_from_name(word).ok_or_else(|| format!("unknown key `{word}`"))
+        }
+        fn button(word: &str) -> Result<MouseButton, String> {
+            keys::mouse_button_from_name(word)
+                .ok_or_else(|| format!("unknown mouse button `{word}`"))
+        }
+
+        let event = match kind {
+            "key_down" | "key_up" => {
+                let key = key(next("a key")?)?;
+                let mut modifiers = KeyMods::default();
+                for word in words.by_ref() {
+                    match word {
+                        "shift" => modifiers.shift = true,
+                        "ctrl" => modifiers.ctrl = true,
+                        "alt" => modifiers.alt = true,
+                        "logo" => modifiers.logo = true,
+                        _ => return Err(format!("unknown modifier `{word}`")),
+                    }
+                }
+                if kind == "key_down" {
+                    InputEvent::KeyDown { key, modifiers }
+                } else {
+                    InputEvent::KeyUp { key, modifiers }
+                }
+            }
+            "mouse_move" => InputEvent::MouseMove(pt2(number(next("x")?)?, number(next("y")?)?)),
+            "mouse_down" | "mouse_up" => {
+                let button = button(next("a mouse button")?)?;
+                let position = pt2(number(next("x")?)?, number(next("y")?)?);
+                if kind == "mouse_down" {
+                    InputEvent::MouseDown { button, position }
+                } else {
+                    InputEvent::MouseUp { button, position }
+                }
+            }
+            "wheel" => InputEvent::MouseWheel(pt2(number(next("x")?)?, number(next("y")?)?)),
+            "touch" => {
+                let phase = match next("a phase")? {
+                    "Started" => TouchPhase::Started,
+                    "Moved" => TouchPhase::Moved,
+                    "Ended" => TouchPhase::Ended,
+                    "Cancelled" => TouchPhase::Cancelled,
+                    word => return Err(format!("unknown touch phase `{word}`")),
+                };
+                let id = number(next("an id")?)?;
+                let position = pt2(number(next("x")?)?, number(next("y")?)?);
+                InputEvent::Touch {
+                    id,
+                    phase,
+                    position,
+                }
+            }
+            "gamepad" => {
+                let pad = number(next("a gamepad")?)?;
+                let event = match next("a gamepad event")? {
+                    "connected" => GamepadEvent::Connected,
+                    "disconnected" => GamepadEvent::Disconnected,
+                    "down" => GamepadEvent::ButtonDown(next("a button")?.parse()?),
+                    "up" => GamepadEvent::ButtonUp(next("a button")?.parse()?),
+                    "axis" => {
+                        let axis = next("an axis")?.parse()?;
+                        GamepadEvent::Axis(axis, number(next("a value")?)?)
+                    }
+                    word => return Err(format!("unknown gamepad event `{word}`")),
+                };
+                InputEvent::Gamepad { pad, event }
+            }
+            _ => return Err(format!("unknown event `{kind}`")),
+        };
+
+        match words.next() {
+            Some(word) => Err(format!("unexpected `{word}`")),
+            None => Ok(event),
+        }
+    }
+}
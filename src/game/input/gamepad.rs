@@ -0,0 +1,413 @@
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    fmt,
+    str::FromStr,
+};
+
+use super::{Binding, Buttons, Input, InputEvent};
+use crate::{game::Game, math::Seconds};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// The bottom face button, A on Xbox pads and Cross on PlayStation ones.
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    Select,
+    Start,
+    /// The button with the console's logo.
+    Mode,
+    /// Pressing the left stick in.
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// Sticks go from -1 to 1, with `y` pointing down like the window. Triggers go
+/// from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadButton {
+    pub const ALL: [Self; 15] = [
+        Self::South,
+        Self::East,
+        Self::West,
+        Self::North,
+        Self::LeftBumper,
+        Self::RightBumper,
+        Self::Select,
+        Self::Start,
+        Self::Mode,
+        Self::LeftStick,
+        Self::RightStick,
+        Self::DPadUp,
+        Self::DPadDown,
+        Self::DPadLeft,
+        Self::DPadRight,
+    ];
+}
+
+impl GamepadAxis {
+    pub const ALL: [Self; 6] = [
+        Self::LeftX,
+        Self::LeftY,
+        Self::RightX,
+        Self::RightY,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+    ];
+}
+
+/// Written the same as the variant's name.
+impl fmt::Display for GamepadButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+/// Case-insensitive.
+impl FromStr for GamepadButton {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|button| button.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown gamepad button `{s}`"))
+    }
+}
+
+/// Written the same as the variant's name.
+impl fmt::Display for GamepadAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+/// Case-insensitive.
+impl FromStr for GamepadAxis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|axis| axis.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown gamepad axis `{s}`"))
+    }
+}
+
+/// Something a gamepad did, reported by a [`GamepadBackend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    Connected,
+    Disconnected,
+    ButtonDown(GamepadButton),
+    ButtonUp(GamepadButton),
+    /// An axis moved to a new value.
+    Axis(GamepadAxis, f32),
+}
+
+/// Where gamepad events come from. Each pad is identified by a number that
+/// stays the same while it is connected.
+///
+/// The default backend is a [`VirtualGamepads`] with nothing connected. Use
+/// [`GameBuilder::gamepad_backend`](crate::game::GameBuilder::gamepad_backend)
+/// to read real devices, for example through a gamepad library.
+pub trait GamepadBackend: Any {
+    /// Add everything that happened since the last poll to `events`, in
+    /// order. Called at the start of every frame.
+    fn poll(&mut self, events: &mut Vec<(usize, GamepadEvent)>);
+    /// Vibrate `pad` with `strength` from 0 to 1 for `duration` seconds.
+    /// Backends that can't rumble ignore it.
+    fn rumble(&mut self, _pad: usize, _strength: f32, _duration: Seconds) {}
+}
+
+/// A rumble requested from a [`VirtualGamepads`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rumble {
+    pub strength: f32,
+    pub duration: Seconds,
+}
+
+/// Gamepads controlled from code instead of devices, to test gameplay that
+/// uses gamepads. Changes show up in [`Input`] on the next frame, like real
+/// ones.
+#[derive(Default)]
+pub struct VirtualGamepads {
+    events: VecDeque<(usize, GamepadEvent)>,
+    /// connected pads, with the last rumble requested on each
+    pads: HashMap<usize, Option<Rumble>>,
+    next_pad: usize,
+}
+
+impl VirtualGamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect a new pad, returning its number.
+    pub fn connect(&mut self) -> usize {
+        let pad = self.next_pad;
+        self.next_pad += 1;
+        self.pads.insert(pad, None);
+        self.events.push_back((pad, GamepadEvent::Connected));
+        pad
+    }
+    pub fn disconnect(&mut self, pad: usize) {
+        if self.pads.remove(&pad).is_some() {
+            self.events.push_back((pad, GamepadEvent::Disconnected));
+        }
+    }
+    pub fn is_connected(&self, pad: usize) -> bool {
+        self.pads.contains_key(&pad)
+    }
+
+    pub fn press(&mut self, pad: usize, button: GamepadButton) {
+        self.send(pad, GamepadEvent::ButtonDown(button));
+    }
+    pub fn release(&mut self, pad: usize, button: GamepadButton) {
+        self.send(pad, GamepadEvent::ButtonUp(button));
+    }
+    pub fn set_axis(&mut self, pad: usize, axis: GamepadAxis, value: f32) {
+        self.send(pad, GamepadEvent::Axis(axis, value));
+    }
+    fn send(&mut self, pad: usize, event: GamepadEvent) {
+        if self.is_connected(pad) {
+            self.events.push_back((pad, event));
+        }
+    }
+
+    /// Last rumble requested on `pad`, if there was one.
+    pub fn last_rumble(&self, pad: usize) -> Option<Rumble> {
+        self.pads.get(&pad).copied().flatten()
+    }
+}
+
+impl GamepadBackend for VirtualGamepads {
+    fn poll(&mut self, events: &mut Vec<(usize, GamepadEvent)>) {
+        events.extend(self.events.drain(..));
+    }
+    fn rumble(&mut self, pad: usize, strength: f32, duration: Seconds) {
+        if let Some(rumble) = self.pads.get_mut(&pad) {
+            *rumble = Some(Rumble { strength, duration });
+        }
+    }
+}
+
+/// State of every connected gamepad, updated at the start of each frame like
+/// the rest of [`Input`].
+pub(crate) struct Gamepads {
+    /// connected pads, in order
+    connected: Vec<usize>,
+    connected_now: Vec<usize>,
+    disconnected_now: Vec<usize>,
+    /// events since the last frame
+    pending: Vec<(usize, GamepadEvent)>,
+
+    buttons: Buttons<(usize, GamepadButton)>,
+    axes: HashMap<(usize, GamepadAxis), f32>,
+    /// axes pushed past the deadzone, in the positive or negative direction,
+    /// so they can be pressed and released like buttons
+    axis_buttons: Buttons<(usize, GamepadAxis, bool)>,
+}
+
+impl Gamepads {
+    pub(crate) fn new() -> Self {
+        Self {
+            connected: Vec::new(),
+            connected_now: Vec::new(),
+            disconnected_now: Vec::new(),
+            pending: Vec::new(),
+            buttons: Buttons::new(),
+            axes: HashMap::new(),
+            axis_buttons: Buttons::new(),
+        }
+    }
+
+    pub(crate) fn add_event(&mut self, pad: usize, event: GamepadEvent) {
+        self.pending.push((pad, event));
+    }
+
+    pub(crate) fn begin_frame(&mut self, deadzone: f32, fixed_timestep: bool) {
+        self.connected_now.clear();
+        self.disconnected_now.clear();
+        for (pad, event) in self.pending.drain(..) {
+            let connected = self.connected.contains(&pad);
+            match event {
+                GamepadEvent::Connected => {
+                    if !connected {
+                        self.connected.push(pad);
+                        self.connected_now.push(pad);
+                    }
+                }
+                GamepadEvent::Disconnected => {
+                    if connected {
+                        self.connected.retain(|p| *p != pad);
+                        self.axes.retain(|(p, _), _| *p != pad);
+                        // A disconnected pad won't report its buttons being
+                        // released
+                        self.buttons.release_where(|(p, _)| *p == pad);
+                        self.disconnected_now.push(pad);
+                    }
+                }
+                // Pads that aren't connected are ignored, like for axes
+                GamepadEvent::ButtonDown(button) if connected => {
+                    self.buttons.set((pad, button), true)
+                }
+                GamepadEvent::ButtonUp(button) if connected => {
+                    self.buttons.set((pad, button), false)
+                }
+                GamepadEvent::Axis(axis, value) if connected => {
+                    self.axes.insert((pad, axis), value.clamp(-1., 1.));
+                }
+                GamepadEvent::ButtonDown(_)
+                | GamepadEvent::ButtonUp(_)
+                | GamepadEvent::Axis(..) => {}
+            }
+        }
+        self.buttons.begin_frame(fixed_timestep);
+
+        let mut changes = Vec::new();
+        for (&(pad, axis), &value) in &self.axes {
+            for positive in [true, false] {
+                let held = if positive { value } else { -value } > deadzone;
+                if held != self.axis_buttons.down.contains(&(pad, axis, positive)) {
+                    changes.push(((pad, axis, positive), held));
+                }
+            }
+        }
+        for &(pad, axis, positive) in &self.axis_buttons.down {
+            if !self.axes.contains_key(&(pad, axis)) {
+                changes.push(((pad, axis, positive), false));
+            }
+        }
+        for (button, held) in changes {
+            self.axis_buttons.set(button, held);
+        }
+        self.axis_buttons.begin_frame(fixed_timestep);
+    }
+    pub(crate) fn end_fixed_step(&mut self) {
+        self.buttons.end_fixed_step();
+        self.axis_buttons.end_fixed_step();
+    }
+}
+
+impl Input {
+    /// Every connected gamepad, in the order they connected.
+    pub fn gamepads(&self) -> &[usize] {
+        &self.gamepads.connected
+    }
+    pub fn is_gamepad_connected(&self, pad: usize) -> bool {
+        self.gamepads.connected.contains(&pad)
+    }
+    /// Gamepads connected since the last frame.
+    pub fn gamepads_connected(&self) -> &[usize] {
+        &self.gamepads.connected_now
+    }
+    /// Gamepads disconnected since the last frame.
+    pub fn gamepads_disconnected(&self) -> &[usize] {
+        &self.gamepads.disconnected_now
+    }
+
+    pub fn is_gamepad_down(&self, pad: usize, button: GamepadButton) -> bool {
+        self.gamepads.buttons.down.contains(&(pad, button))
+    }
+    /// Whether the button was pressed since the last update.
+    pub fn is_gamepad_pressed(&self, pad: usize, button: GamepadButton) -> bool {
+        self.gamepads.buttons.is_pressed((pad, button), self.fixed)
+    }
+    /// Whether the button was released since the last update.
+    pub fn is_gamepad_released(&self, pad: usize, button: GamepadButton) -> bool {
+        self.gamepads.buttons.is_released((pad, button), self.fixed)
+    }
+    /// Value of an axis, 0 if the pad isn't connected. The deadzone isn't
+    /// applied.
+    pub fn gamepad_axis(&self, pad: usize, axis: GamepadAxis) -> f32 {
+        self.gamepads.axes.get(&(pad, axis)).copied().unwrap_or(0.)
+    }
+    pub(crate) fn is_any_gamepad_down(&self, button: GamepadButton) -> bool {
+        let down = &self.gamepads.buttons.down;
+        down.iter().any(|(_, b)| *b == button)
+    }
+    pub(crate) fn is_any_gamepad_pressed(&self, button: GamepadButton) -> bool {
+        let pressed = self.gamepads.buttons.pressed(self.fixed);
+        pressed.iter().any(|(_, b)| *b == button)
+    }
+    pub(crate) fn is_any_gamepad_released(&self, button: GamepadButton) -> bool {
+        let released = self.gamepads.buttons.released(self.fixed);
+        released.iter().any(|(_, b)| *b == button)
+    }
+    /// Whether an axis is past the deadzone in a direction on any pad.
+    pub(crate) fn is_any_axis_down(&self, axis: GamepadAxis, positive: bool) -> bool {
+        let down = &self.gamepads.axis_buttons.down;
+        down.iter().any(|(_, a, p)| (*a, *p) == (axis, positive))
+    }
+    pub(crate) fn is_any_axis_pressed(&self, axis: GamepadAxis, positive: bool) -> bool {
+        let pressed = self.gamepads.axis_buttons.pressed(self.fixed);
+        pressed.iter().any(|(_, a, p)| (*a, *p) == (axis, positive))
+    }
+    pub(crate) fn is_any_axis_released(&self, axis: GamepadAxis, positive: bool) -> bool {
+        let released = self.gamepads.axis_buttons.released(self.fixed);
+        released
+            .iter()
+            .any(|(_, a, p)| (*a, *p) == (axis, positive))
+    }
+    /// Strongest value of an axis in a direction on any pad, from 0 to 1.
+    pub(crate) fn any_gamepad_axis(&self, axis: GamepadAxis, positive: bool) -> f32 {
+        let sign = if positive { 1. } else { -1. };
+        self.gamepads
+            .axes
+            .iter()
+            .filter(|((_, a), _)| *a == axis)
+            .map(|(_, value)| (value * sign).max(0.))
+            .fold(0., f32::max)
+    }
+
+    /// A gamepad button pressed, or axis pushed past the deadzone, since the
+    /// last update.
+    pub(crate) fn pressed_gamepad_binding(&self) -> Option<Binding> {
+        let buttons = self.gamepads.buttons.pressed(self.fixed).iter();
+        let axes = self.gamepads.axis_buttons.pressed(self.fixed).iter();
+        buttons
+            .map(|&(_, button)| Binding::Gamepad(button))
+            .chain(axes.map(|&(_, axis, positive)| Binding::Axis { axis, positive }))
+            .next()
+    }
+}
+
+impl Game {
+    /// Vibrate `pad` with `strength` from 0 to 1 for `duration` seconds, if
+    /// the gamepad backend supports it.
+    pub fn rumble(&mut self, pad: usize, strength: f32, duration: Seconds) {
+        self.gamepad_backend
+            .rumble(pad, strength.clamp(0., 1.), duration);
+    }
+
+    /// The gamepad backend, if it is a `B`. Useful to drive
+    /// [`VirtualGamepads`] in tests.
+    pub fn gamepad_backend<B: GamepadBackend>(&mut self) -> Option<&mut B> {
+        (&mut *self.gamepad_backend as &mut dyn Any).downcast_mut()
+    }
+
+    /// Report what the gamepads did since the last frame.
+    pub(crate) fn poll_gamepads(&mut self) {
+        let mut events = Vec::new();
+        self.gamepad_backend.poll(&mut events);
+        for (pad, event) in events {
+            self.window_input(InputEvent::Gamepad { pad, event });
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use super::{
+    touch::{Touch, TouchPhase},
+    Input,
+};
+use crate::math::point::Pt2;
+
+/// A gesture recognized from touches this frame. Positions are in window
+/// pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A finger touched the screen and left it quickly without moving.
+    Tap { position: Pt2 },
+    /// A finger has been held on the screen without moving. Recognized once,
+    /// while the finger is still down.
+    LongPress { position: Pt2 },
+    /// A finger moved quickly across the screen and left it.
+    Swipe { start: Pt2, end: Pt2 },
+    /// Two fingers moved closer together or further apart. `scale` is how much
+    /// the distance between them changed this frame, above 1 when spreading.
+    Pinch { center: Pt2, scale: f32 },
+}
+
+impl Gesture {
+    /// Most distance in pixels a finger can move and still tap or long press.
+    pub const TAP_SLOP: f32 = 10.;
+    /// Longest time in seconds a tap can take.
+    pub const TAP_TIME: f64 = 0.3;
+    /// Time in seconds a finger has to be held for a long press.
+    pub const LONG_PRESS_TIME: f64 = 0.5;
+    /// Least distance in pixels a swipe covers.
+    pub const SWIPE_DISTANCE: f32 = 50.;
+    /// Longest time in seconds a swipe can take.
+    pub const SWIPE_TIME: f64 = 0.5;
+}
+
+pub(crate) struct Gestures {
+    recognized: Vec<Gesture>,
+}
+
+impl Gestures {
+    pub(crate) fn new() -> Self {
+        Self {
+            recognized: Vec::new(),
+        }
+    }
+
+    pub(crate) fn recognize(&mut self, touches: &mut [Touch], now: f64) {
+        self.recognized.clear();
+
+        for touch in touches.iter_mut() {
+            let moved = (touch.position - touch.start_position).length();
+            if moved > Gesture::TAP_SLOP {
+                touch.moved_far = true;
+            }
+            let duration = now - touch.start_time;
+
+            match touch.phase {
+                TouchPhase::Ended => {
+                    if !touch.moved_far && !touch.long_pressed && duration <= Gesture::TAP_TIME {
+                        self.recognized.push(Gesture::Tap {
+                            position: touch.position,
+                        });
+                    } else if moved >= Gesture::SWIPE_DISTANCE && duration <= Gesture::SWIPE_TIME {
+                        self.recognized.push(Gesture::Swipe {
+                            start: touch.start_position,
+                            end: touch.position,
+                        });
+                    }
+                }
+                TouchPhase::Cancelled => {}
+                _ => {
+                    if !touch.moved_far
+                        && !touch.long_pressed
+                        && duration >= Gesture::LONG_PRESS_TIME
+                    {
+                        touch.long_pressed = true;
+                        self.recognized.push(Gesture::LongPress {
+                            position: touch.position,
+                        });
+                    }
+                }
+            }
+        }
+
+        let active: Vec<&Touch> = touches.iter().filter(|t| t.is_active()).collect();
+        if let [a, b] = active[..] {
+            let moved = a.phase == TouchPhase::Moved || b.phase == TouchPhase::Moved;
+            let before = (a.previous_position - b.previous_position).length();
+            if moved && before > 0. {
+                self.recognized.push(Gesture::Pinch {
+                    center: (a.position + b.position) / 2.,
+                    scale: (a.position - b.position).length() / before,
+                });
+            }
+        }
+    }
+}
+
+impl Input {
+    /// Gestures recognized this frame.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.touches.gestures.recognized
+    }
+}
@@ -0,0 +1,56 @@
+use super::{KeyCode, MouseButton};
+
+macro_rules! key_names {
+    ($($key:ident),* $(,)?) => {
+        /// Every key with the name it is saved as.
+        const KEY_NAMES: &[(&str, KeyCode)] = &[$((stringify!($key), KeyCode::$key)),*];
+    };
+}
+
+key_names! {
+    Space, Apostrophe, Comma, Minus, Period, Slash, Key0, Key1, Key2, Key3, Key4, Key5, Key6,
+    Key7, Key8, Key9, Semicolon, Equal, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S,
+    T, U, V, W, X, Y, Z, LeftBracket, Backslash, RightBracket, GraveAccent, World1, World2,
+    Escape, Enter, Tab, Backspace, Insert, Delete, Right, Left, Down, Up, PageUp, PageDown,
+    Home, End, CapsLock, ScrollLock, NumLock, PrintScreen, Pause, F1, F2, F3, F4, F5, F6, F7,
+    F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, F25, Kp0,
+    Kp1, Kp2, Kp3, Kp4, Kp5, Kp6, Kp7, Kp8, Kp9, KpDecimal, KpDivide, KpMultiply, KpSubtract,
+    KpAdd, KpEnter, KpEqual, LeftShift, LeftControl, LeftAlt, LeftSuper, RightShift,
+    RightControl, RightAlt, RightSuper, Menu, Back, Unknown,
+}
+
+pub(crate) fn key_name(key: KeyCode) -> &'static str {
+    KEY_NAMES
+        .iter()
+        .find(|(_, k)| *k == key)
+        .map_or("Unknown", |(name, _)| name)
+}
+
+pub(crate) fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+/// Every mouse button with the name it is saved as.
+const MOUSE_BUTTON_NAMES: &[(&str, MouseButton)] = &[
+    ("Left", MouseButton::Left),
+    ("Middle", MouseButton::Middle),
+    ("Right", MouseButton::Right),
+    ("Unknown", MouseButton::Unknown),
+];
+
+pub(crate) fn mouse_button_name(button: MouseButton) -> &'static str {
+    MOUSE_BUTTON_NAMES
+        .iter()
+        .find(|(_, b)| *b == button)
+        .map_or("Unknown", |(name, _)| name)
+}
+
+pub(crate) fn mouse_button_from_name(name: &str) -> Option<MouseButton> {
+    MOUSE_BUTTON_NAMES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, button)| *button)
+}
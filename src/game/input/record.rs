@@ -0,0 +1,255 @@
+use std::{fmt, fs, path::Path};
+
+use super::InputEvent;
+use crate::{game::Game, math::Seconds, Error, Result};
+
+/// One frame of a [`Recording`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    /// Real time the frame took, before time scale.
+    pub delta: Seconds,
+    /// Input events that arrived before the frame, in order.
+    pub events: Vec<InputEvent>,
+    /// Checksum of the game after the frame's update, if a checksum was set
+    /// with [`Game::set_checksum`].
+    pub checksum: Option<u64>,
+}
+
+/// Every input event and frame length of a session, so it can be replayed
+/// exactly. Start recording before the first frame, and replay on a game
+/// built the same way, for the replay to match.
+///
+/// Recordings are saved as text. Each frame starts with a line like
+/// `frame 0.016666668 12345`, with its delta and checksum, followed by its
+/// events written like in an [`InputScript`](super::InputScript).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut recording = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message| Error::Parse {
+                what: "recording",
+                line: i + 1,
+                message,
+            };
+
+            if let Some(frame) = line.strip_prefix("frame ") {
+                let mut words = frame.split_whitespace();
+                let delta = words
+                    .next()
+                    .and_then(|d| d.parse().ok())
+                    .ok_or_else(|| error("expected the frame's delta".to_owned()))?;
+                let checksum = match words.next() {
+                    Some(sum) => Some(
+                        sum.parse()
+                            .map_err(|_| error(format!("expected a checksum, found `{sum}`")))?,
+                    ),
+                    None => None,
+                };
+                recording.frames.push(RecordedFrame {
+                    delta,
+                    events: Vec::new(),
+                    checksum,
+                });
+            } else {
+                let frame = recording
+                    .frames
+                    .last_mut()
+                    .ok_or_else(|| error("expected a frame before its events".to_owned()))?;
+                frame.events.push(line.parse().map_err(error)?);
+            }
+        }
+        Ok(recording)
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+    pub fn save_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in &self.frames {
+            write!(f, "frame {}", frame.delta)?;
+            if let Some(checksum) = frame.checksum {
+                write!(f, " {checksum}")?;
+            }
+            writeln!(f)?;
+            for event in &frame.events {
+                writeln!(f, "{event}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+type Checksum = Box<dyn Fn(&Game) -> u64>;
+
+/// Recording and playback state of a [`Game`].
+#[derive(Default)]
+pub(crate) struct Recorder {
+    recording: Option<Recording>,
+    /// events since the last frame
+    pending: Vec<InputEvent>,
+    checksum: Option<Checksum>,
+
+    playback: Option<Playback>,
+}
+
+struct Playback {
+    recording: Recording,
+    next_frame: usize,
+    /// frame injected for the current update, if it started after playback
+    current: Option<usize>,
+    /// whether a frame already didn't match the recording
+    diverged: bool,
+}
+
+impl Game {
+    /// Start recording input and frame lengths. Anything recorded before is
+    /// dropped.
+    pub fn start_recording(&mut self) {
+        self.recorder.recording = Some(Recording::default());
+        self.recorder.pending.clear();
+    }
+    /// Stop recording, returning what was recorded.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.recording.take()
+    }
+    pub fn is_recording(&self) -> bool {
+        self.recorder.recording.is_some()
+    }
+
+    /// Sum up the state of the game after every update, to find where a
+    /// replay stops matching its recording. The checksum should change when
+    /// anything that matters changes, like positions or scores.
+    pub fn set_checksum(&mut self, checksum: impl Fn(&Game) -> u64 + 'static) {
+        self.recorder.checksum = Some(Box::new(checksum));
+    }
+    fn checksum(&self) -> Option<u64> {
+        self.recorder
+            .checksum
+            .as_ref()
+            .map(|checksum| checksum(self))
+    }
+
+    /// Run every frame of `recording` right away, with its input and frame
+    /// lengths. Fails with [`Error::ReplayDiverged`] on the first frame whose
+    /// checksum doesn't match the recording.
+    pub fn replay(&mut self, recording: &Recording) -> Result<()> {
+        for (frame, recorded) in recording.frames.iter().enumerate() {
+            for event in &recorded.events {
+                self.inject(*event);
+            }
+            self.step(recorded.delta);
+            check_frame(frame, recorded.checksum, self.checksum())?;
+        }
+        Ok(())
+    }
+    /// Play `recording` back in the window at its own pace. Input from the
+    /// player is ignored until it ends. If the game stops matching the
+    /// recording, the first frame where it did is printed to stderr.
+    pub fn play_recording(&mut self, recording: Recording) {
+        self.recorder.playback = Some(Playback {
+            recording,
+            next_frame: 0,
+            current: None,
+            diverged: false,
+        });
+    }
+    pub fn is_playing_recording(&self) -> bool {
+        self.recorder.playback.is_some()
+    }
+
+    /// Input from the window, which is ignored while playing a recording.
+    pub(crate) fn window_input(&mut self, event: InputEvent) {
+        if !self.is_playing_recording() {
+            self.inject(event);
+        }
+    }
+    pub(crate) fn record_input(&mut self, event: InputEvent) {
+        if self.recorder.recording.is_some() {
+            self.recorder.pending.push(event);
+        }
+    }
+
+    /// Inject the next frame of the recording being played, returning its
+    /// length.
+    pub(crate) fn next_playback_frame(&mut self) -> Option<Seconds> {
+        let playback = self.recorder.playback.as_mut()?;
+        let Some(frame) = playback.recording.frames.get(playback.next_frame) else {
+            self.recorder.playback = None;
+            return None;
+        };
+        let (delta, events) = (frame.delta, frame.events.clone());
+        playback.current = Some(playback.next_frame);
+        playback.next_frame += 1;
+        for event in events {
+            self.inject(event);
+        }
+        Some(delta)
+    }
+
+    /// Called after every update, to record or check the frame.
+    pub(crate) fn end_recorded_frame(&mut self, delta: Seconds) {
+        if self.recorder.recording.is_some() {
+            let frame = RecordedFrame {
+                delta,
+                events: std::mem::take(&mut self.recorder.pending),
+                checksum: self.checksum(),
+            };
+            if let Some(recording) = &mut self.recorder.recording {
+                recording.frames.push(frame);
+            }
+        }
+
+        let Some(frame) = self
+            .recorder
+            .playback
+            .as_mut()
+            .and_then(|p| p.current.take())
+        else {
+            return;
+        };
+        let found = self.checksum();
+        if let Some(playback) = &mut self.recorder.playback {
+            let expected = playback.recording.frames[frame].checksum;
+            if let Err(err) = check_frame(frame, expected, found) {
+                if !playback.diverged {
+                    playback.diverged = true;
+                    err.report();
+                }
+            }
+        }
+    }
+}
+
+fn check_frame(frame: usize, expected: Option<u64>, found: Option<u64>) -> Result<()> {
+    match (expected, found) {
+        (Some(expected), Some(found)) if expected != found => Err(Error::ReplayDiverged {
+            frame,
+            expected,
+            found,
+        }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fmt, fs, path::Path};
+
+use super::InputEvent;
+use crate::{game::Game, math::Seconds, Error, Result};
+
+/// Input events to inject on set frames, for repeatable tests of headless
+/// games.
+///
+/// Scripts are written one event per line, after the frame it happens on.
+/// Frames count from 0 when the script starts running. Empty lines and lines
+/// starting with `#` are skipped. Events are written as shown on
+/// [`InputEvent`], so typing Enter is `char U+000D`.
+///
+/// ```text
+/// # jump, then walk right
+/// 0: key_down Space
+/// 1: key_up Space
+/// 10: key_down D
+/// 40: key_up D
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputScript {
+    /// sorted by frame, and by the order they were added within a frame
+    events: Vec<(u64, InputEvent)>,
+}
+
+impl InputScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an event on `frame`, after the ones already on it.
+    pub fn at(mut self, frame: u64, event: InputEvent) -> Self {
+        self.push(frame, event);
+        self
+    }
+    /// Add an event on `frame`, after the ones already on it.
+    pub fn push(&mut self, frame: u64, event: InputEvent) {
+        let index = self.events.partition_point(|(f, _)| *f <= frame);
+        self.events.insert(index, (frame, event));
+    }
+
+    pub fn events(&self) -> &[(u64, InputEvent)] {
+        &self.events
+    }
+    /// Number of frames the script runs for, up to and including the frame
+    /// of its last event.
+    pub fn frames(&self) -> u64 {
+        self.events.last().map_or(0, |(frame, _)| frame + 1)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut script = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim_start();
+            if line.trim_end().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message| Error::Parse {
+                what: "input script",
+                line: i + 1,
+                message,
+            };
+
+            let (frame, event) = line
+                .split_once(':')
+                .ok_or_else(|| error("expected `frame: event`".to_owned()))?;
+            let frame = frame
+                .trim()
+                .parse()
+                .map_err(|_| error(format!("expected a frame number, found `{frame}`")))?;
+            // Only one space is skipped, since `char` events can type a space
+            let event = event.strip_prefix(' ').unwrap_or(event);
+            script.push(frame, event.parse().map_err(error)?);
+        }
+        Ok(script)
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+    pub fn save_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+impl fmt::Display for InputScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (frame, event) in &self.events {
+            writeln!(f, "{frame}: {event}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Run `script` from the next frame, stepping `delta` seconds per frame
+    /// until every event has been seen by an update. Events on a frame are
+    /// injected right before it.
+    pub fn run_script(&mut self, script: &InputScript, delta: Seconds) {
+        let mut events = script.events.iter().peekable();
+        for frame in 0..script.frames() {
+            while let Some((_, event)) = events.next_if(|(f, _)| *f == frame) {
+                self.inject(*event);
+            }
+            self.step(delta);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use super::{gesture::Gestures, Input, MouseButton};
+use crate::{game::Game, math::point::Pt2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched the screen this frame.
+    Started,
+    /// The finger moved this frame.
+    Moved,
+    /// The finger is on the screen, but didn't move this frame.
+    Stationary,
+    /// The finger left the screen this frame.
+    Ended,
+    /// The system took over the touch, for example for a system gesture.
+    Cancelled,
+}
+
+impl From<miniquad::TouchPhase> for TouchPhase {
+    fn from(phase: miniquad::TouchPhase) -> Self {
+        match phase {
+            miniquad::TouchPhase::Started => Self::Started,
+            miniquad::TouchPhase::Moved => Self::Moved,
+            miniquad::TouchPhase::Ended => Self::Ended,
+            miniquad::TouchPhase::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// A finger on the screen. Positions are in window pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct Touch {
+    /// Stays the same while the finger is down.
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Pt2,
+    /// Position at the start of the frame.
+    pub previous_position: Pt2,
+    pub start_position: Pt2,
+    /// Unscaled time the touch started at, from [`Time::unscaled_elapsed`](crate::game::time::Time::unscaled_elapsed).
+    pub start_time: f64,
+
+    /// whether the touch has moved too far to be a tap or long press
+    pub(crate) moved_far: bool,
+    pub(crate) long_pressed: bool,
+}
+
+impl Touch {
+    pub fn is_active(&self) -> bool {
+        !matches!(self.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+    }
+}
+
+/// Every finger on the screen, updated at the start of each frame like the
+/// rest of [`Input`].
+pub(crate) struct Touches {
+    pub(crate) touches: Vec<Touch>,
+    pending: Vec<(u64, TouchPhase, Pt2)>,
+    pub(crate) gestures: Gestures,
+
+    /// emulate the mouse with the first finger
+    pub(crate) emulate_mouse: bool,
+    /// finger currently emulating the mouse
+    mouse_touch: Option<u64>,
+}
+
+impl Touches {
+    pub(crate) fn new() -> Self {
+        Self {
+            touches: Vec::new(),
+            pending: Vec::new(),
+            gestures: Gestures::new(),
+            emulate_mouse: true,
+            mouse_touch: None,
+        }
+    }
+
+    pub(crate) fn begin_frame(&mut self, now: f64) {
+        self.touches.retain(Touch::is_active);
+        for touch in &mut self.touches {
+            touch.phase = TouchPhase::Stationary;
+            touch.previous_position = touch.position;
+        }
+
+        for (id, phase, position) in self.pending.drain(..) {
+            let existing = self.touches.iter_mut().find(|t| t.id == id);
+            match (phase, existing) {
+                (TouchPhase::Started, existing) => {
+                    let touch = Touch {
+                        id,
+                        phase,
+                        position,
+                        previous_position: position,
+                        start_position: position,
+                        start_time: now,
+                        moved_far: false,
+                        long_pressed: false,
+                    };
+                    match existing {
+                        Some(existing) => *existing = touch,
+                        None => self.touches.push(touch),
+                    }
+                }
+                (TouchPhase::Stationary, _) | (_, None) => {}
+                (phase, Some(touch)) => {
+                    touch.position = position;
+                    // A touch that started this frame stays started
+                    if touch.phase != TouchPhase::Started || phase != TouchPhase::Moved {
+                        touch.phase = phase;
+                    }
+                }
+            }
+        }
+
+        self.gestures.recognize(&mut self.touches, now);
+    }
+}
+
+impl Input {
+    /// Every finger on the screen, in the order they touched it. Fingers that
+    /// left the screen stay for the frame they left in.
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches.touches
+    }
+    pub fn touch(&self, id: u64) -> Option<&Touch> {
+        self.touches.touches.iter().find(|t| t.id == id)
+    }
+    /// Whether the first finger on the screen also acts as the left mouse
+    /// button. Defaults to `true`.
+    pub fn set_touch_emulates_mouse(&mut self, emulate: bool) {
+        self.touches.emulate_mouse = emulate;
+    }
+}
+
+impl Game {
+    /// Queue a touch, moving the mouse with it if it is emulated.
+    pub(crate) fn add_touch(&mut self, id: u64, phase: TouchPhase, position: Pt2) {
+        let touches = &mut self.input.touches;
+        touches.pending.push((id, phase, position));
+
+        if !touches.emulate_mouse {
+            return;
+        }
+        match phase {
+            TouchPhase::Started if touches.mouse_touch.is_none() => {
+                touches.mouse_touch = Some(id);
+                self.set_mouse_position(position);
+                self.input.set_mb_down(MouseButton::Left);
+            }
+            TouchPhase::Moved if touches.mouse_touch == Some(id) => {
+                self.set_mouse_position(position);
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled if touches.mouse_touch == Some(id) => {
+                touches.mouse_touch = None;
+                self.set_mouse_position(position);
+                self.input.set_mb_up(MouseButton::Left);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::{any::Any, mem};
+
+use crate::{
+    self as mozart,
+    obj::{Make, MakeDefault, Obj},
+    Result,
+};
+
+use super::Game;
+
+type MakeScene = Box<dyn FnOnce(&mut Game) -> Result<Box<dyn Obj>>>;
+/// A hook waiting for its scene to finish running another one.
+pub(crate) type SceneHook = Box<dyn FnOnce(&mut dyn Obj, &mut Game)>;
+
+/// Holds a scene's place in the stack while one of its hooks runs.
+#[derive(Obj)]
+struct Vacant;
+
+/// A change to the scene stack, applied after the current update finishes.
+pub(crate) enum SceneChange {
+    Replace(MakeScene),
+    Push(MakeScene),
+    Pop,
+}
+
+impl SceneChange {
+    fn make<S>(config: S::Config) -> MakeScene
+    where
+        S: Make + 'static,
+        S::Config: 'static,
+    {
+        Box::new(move |game| Ok(Box::new(S::make(game, config)?)))
+    }
+    fn make_default<S>() -> MakeScene
+    where
+        S: MakeDefault + 'static,
+    {
+        Box::new(|game| Ok(Box::new(S::make_default(game)?)))
+    }
+}
+
+impl Game {
+    /// Replace the current scene with a new one. The old scene is dropped
+    /// before the new one is made, once the current update has finished.
+    pub fn change_scene<S: MakeDefault + 'static>(&mut self) {
+        self.scene_changes
+            .push(SceneChange::Replace(SceneChange::make_default::<S>()));
+    }
+    /// Same as [`Game::change_scene`], with a config for the new scene.
+    pub fn change_scene_with<S>(&mut self, config: S::Config)
+    where
+        S: Make + 'static,
+        S::Config: 'static,
+    {
+        self.scene_changes
+            .push(SceneChange::Replace(SceneChange::make::<S>(config)));
+    }
+
+    /// Put a new scene on top of the current one. Only the top scene is
+    /// updated, but every scene in the stack is drawn, bottom to top.
+    pub fn push_scene<S: MakeDefault + 'static>(&mut self) {
+        self.scene_changes
+            .push(SceneChange::Push(SceneChange::make_default::<S>()));
+    }
+    /// Same as [`Game::push_scene`], with a config for the new scene.
+    pub fn push_scene_with<S>(&mut self, config: S::Config)
+    where
+        S: Make + 'static,
+        S::Config: 'static,
+    {
+        self.scene_changes
+            .push(SceneChange::Push(SceneChange::make::<S>(config)));
+    }
+
+    /// Drop the top scene, returning to the one below it.
+    pub fn pop_scene(&mut self) {
+        self.scene_changes.push(SceneChange::Pop);
+    }
+
+    /// Number of scenes in the stack, including the one being updated.
+    pub fn scene_count(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Apply scene changes in the order they were requested. Changes requested
+    /// while making a new scene are applied once the rest are done. If a scene
+    /// fails to be made, the game fails and the other changes are dropped.
+    pub(crate) fn apply_scene_changes(&mut self) {
+        while !self.scene_changes.is_empty() {
+            let changes = std::mem::take(&mut self.scene_changes);
+            for change in changes {
+                let result = match change {
+                    SceneChange::Replace(make) => {
+                        self.exit_top_scene();
+                        self.ready_scene(make)
+                    }
+                    SceneChange::Push(make) => self.ready_scene(make),
+                    SceneChange::Pop => {
+                        self.exit_top_scene();
+                        Ok(())
+                    }
+                };
+                if let Err(err) = result {
+                    self.fail(err);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn ready_scene(&mut self, make: MakeScene) -> Result<()> {
+        let mut scene = make(self)?;
+        scene.ready_children(self);
+        self.scenes.push(scene);
+        Ok(())
+    }
+    fn exit_top_scene(&mut self) {
+        if let Some(mut scene) = self.scenes.pop() {
+            scene.exit_children(self);
+        }
+    }
+
+    /// Exit and drop every scene, top to bottom, once the game has quit.
+    /// Must not be called while a scene is running a hook, see
+    /// [`Game::exit_if_quit`].
+    pub(crate) fn exit_scenes(&mut self) {
+        debug_assert!(!self.is_scene_running(), "exited scenes during a hook");
+        while !self.scenes.is_empty() {
+            self.exit_top_scene();
+        }
+        self.scene_changes.clear();
+        self.scene_hooks.clear();
+    }
+    /// Exit every scene if the game has quit, unless a scene is running a
+    /// hook. Then it happens once the hook returns.
+    pub(crate) fn exit_if_quit(&mut self) {
+        if self.has_quit() && !self.is_scene_running() {
+            self.exit_scenes();
+        }
+    }
+
+    /// Run `hook` on every scene, bottom to top. A scene that is already
+    /// running a hook, like the top scene during its update, gets it once
+    /// that hook returns.
+    pub(crate) fn for_each_scene(
+        &mut self,
+        hook: impl FnOnce(&mut dyn Obj, &mut Game) + Clone + 'static,
+    ) {
+        for index in 0..self.scenes.len() {
+            if self.is_vacant(index) {
+                self.scene_hooks.push((index, Box::new(hook.clone())));
+            } else {
+                self.with_scene(index, hook.clone());
+            }
+        }
+        self.exit_if_quit();
+    }
+
+    /// Run `hook` on the scene at `index`. The scene is taken out of the stack
+    /// while it runs, since the hook gets the game too, and a placeholder
+    /// keeps its place so the rest of the stack stays where it was.
+    pub(crate) fn with_scene<R>(
+        &mut self,
+        index: usize,
+        hook: impl FnOnce(&mut dyn Obj, &mut Game) -> R,
+    ) -> R {
+        let mut scene = mem::replace(&mut self.scenes[index], Box::new(Vacant));
+        let result = hook(scene.as_mut(), self);
+        // Scene changes are queued until hooks are done, so the place is
+        // still there
+        let vacant: &dyn Any = self.scenes[index].as_ref();
+        debug_assert!(vacant.is::<Vacant>(), "scene stack changed during a hook");
+        self.scenes[index] = scene;
+
+        while let Some(at) = self.scene_hooks.iter().position(|(i, _)| *i == index) {
+            let (_, hook) = self.scene_hooks.remove(at);
+            self.with_scene(index, hook);
+        }
+        result
+    }
+
+    /// Whether the scene at `index` is out of the stack running a hook.
+    fn is_vacant(&self, index: usize) -> bool {
+        let scene: &dyn Any = self.scenes[index].as_ref();
+        scene.is::<Vacant>()
+    }
+    fn is_scene_running(&self) -> bool {
+        (0..self.scenes.len()).any(|index| self.is_vacant(index))
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+use super::{Game, GameBuilder};
+
+/// A global service, registered once per type with
+/// [`GameBuilder::add_server`] or [`Game::add_server`]. Objects can borrow it
+/// with [`Game::server`] and [`Game::server_mut`].
+///
+/// A server is taken out of the registry while its own hooks run, so it
+/// can't be borrowed from the game passed to them.
+pub trait Server: Any {
+    /// Called every frame before the scene is updated.
+    fn pre_update(&mut self, _game: &mut Game) {}
+    /// Called every frame after the scene is updated.
+    fn post_update(&mut self, _game: &mut Game) {}
+}
+
+pub(crate) struct Servers {
+    servers: HashMap<TypeId, Box<dyn Server>>,
+    /// types in the order they were added, which hooks run in
+    order: Vec<TypeId>,
+}
+
+impl Servers {
+    pub(crate) fn new() -> Self {
+        Self {
+            servers: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, server: Box<dyn Server>) {
+        let key = (*server).type_id();
+        if self.servers.insert(key, server).is_none() {
+            self.order.push(key);
+        }
+    }
+}
+
+impl GameBuilder {
+    /// Register a server. Adding a second server of the same type replaces
+    /// the first.
+    pub fn add_server<S: Server>(mut self, server: S) -> Self {
+        self.servers.push(Box::new(server));
+        self
+    }
+}
+
+impl Game {
+    /// Register a server. Adding a second server of the same type replaces
+    /// the first.
+    pub fn add_server<S: Server>(&mut self, server: S) {
+        self.servers.insert(Box::new(server));
+    }
+    pub fn remove_server<S: Server>(&mut self) -> Option<S> {
+        let key = TypeId::of::<S>();
+        let server: Box<dyn Any> = self.servers.servers.remove(&key)?;
+        self.servers.order.retain(|&k| k != key);
+        Some(*server.downcast().expect("server stored under its own type"))
+    }
+
+    pub fn server<S: Server>(&self) -> Option<&S> {
+        let server: &dyn Any = self.servers.servers.get(&TypeId::of::<S>())?.as_ref();
+        server.downcast_ref()
+    }
+    pub fn server_mut<S: Server>(&mut self) -> Option<&mut S> {
+        let server: &mut dyn Any = self.servers.servers.get_mut(&TypeId::of::<S>())?.as_mut();
+        server.downcast_mut()
+    }
+
+    fn run_server_hooks(&mut self, hook: fn(&mut dyn Server, &mut Game)) {
+        for key in self.servers.order.clone() {
+            let Some(mut server) = self.servers.servers.remove(&key) else {
+                continue;
+            };
+            hook(server.as_mut(), self);
+
+            // Unless it was removed or replaced in the hook, put it back
+            if self.servers.order.contains(&key) {
+                self.servers.servers.entry(key).or_insert(server);
+            }
+        }
+    }
+    pub(crate) fn pre_update_servers(&mut self) {
+        self.run_server_hooks(|server, game| server.pre_update(game));
+    }
+    pub(crate) fn post_update_servers(&mut self) {
+        self.run_server_hooks(|server, game| server.post_update(game));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::math::Seconds;
+
+use super::Game;
+
+/// When an object is updated, depending on whether the game is paused. Set it
+/// with `#[process_mode(..)]` next to `#[derive(Obj)]`. Children are updated
+/// only if their parent is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessMode {
+    /// Same as the parent object. Scenes that inherit are [`Pausable`](ProcessMode::Pausable).
+    #[default]
+    Inherit,
+    /// Only updated while the game is not paused.
+    Pausable,
+    /// Always updated.
+    Always,
+    /// Only updated while the game is paused, for things like pause menus.
+    WhenPaused,
+}
+
+/// Clock of a [`Game`]. Scaled time is multiplied by the time scale and stops
+/// while the game is paused, unscaled time always follows the real clock.
+pub struct Time {
+    scale: f32,
+    paused: bool,
+
+    elapsed: f64,
+    unscaled_elapsed: f64,
+    delta: Seconds,
+    unscaled_delta: Seconds,
+    frame: u64,
+
+    /// length of one fixed update, if a fixed timestep is used
+    pub(crate) fixed_delta: Option<Seconds>,
+    /// time that has passed but not been consumed by fixed updates yet
+    pub(crate) accumulator: Seconds,
+    /// process mode of the object currently being updated
+    process_mode: ProcessMode,
+}
+
+impl Time {
+    pub(crate) fn new(fixed_delta: Option<Seconds>) -> Self {
+        Self {
+            scale: 1.,
+            paused: false,
+            elapsed: 0.,
+            unscaled_elapsed: 0.,
+            delta: 0.,
+            unscaled_delta: 0.,
+            frame: 0,
+            fixed_delta,
+            accumulator: 0.,
+            process_mode: ProcessMode::Pausable,
+        }
+    }
+
+    /// Start a new frame, returning the scaled delta.
+    pub(crate) fn advance(&mut self, unscaled_delta: Seconds) -> Seconds {
+        self.frame += 1;
+        self.unscaled_delta = unscaled_delta;
+        self.unscaled_elapsed += unscaled_delta as f64;
+        self.delta = unscaled_delta * self.scale;
+        if !self.paused {
+            self.elapsed += self.delta as f64;
+        }
+        self.delta
+    }
+
+    /// Seconds of scaled time the game has run for, not counting pauses.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+    /// Seconds of real time the game has run for.
+    pub fn unscaled_elapsed(&self) -> f64 {
+        self.unscaled_elapsed
+    }
+    /// Scaled length of the current frame. This is what pausable objects'
+    /// updates receive.
+    pub fn delta(&self) -> Seconds {
+        self.delta
+    }
+    /// Real length of the current frame. Objects that are also updated while
+    /// the game is paused receive this, so they keep moving at any time
+    /// scale.
+    pub fn unscaled_delta(&self) -> Seconds {
+        self.unscaled_delta
+    }
+    /// Number of frames updated so far, including the current one.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl Game {
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
+    /// Speed up or slow down the game. `0.5` runs at half speed.
+    pub fn set_time_scale(&mut self, scale: f32) {
+        assert!(scale >= 0., "time scale can't be negative");
+        self.time.scale = scale;
+    }
+
+    /// Pause or unpause the game. While paused, only objects with
+    /// [`ProcessMode::Always`] or [`ProcessMode::WhenPaused`] are updated, and
+    /// fixed updates don't run.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.time.paused = paused;
+    }
+    pub fn is_paused(&self) -> bool {
+        self.time.paused
+    }
+
+    /// Called by `#[derive(Obj)]` before updating an object. Returns the
+    /// parent's mode to restore afterwards, or `None` if the object should
+    /// not be updated.
+    #[doc(hidden)]
+    pub fn enter_process_mode(&mut self, mode: ProcessMode) -> Option<ProcessMode> {
+        let parent = self.time.process_mode;
+        let mode = match mode {
+            ProcessMode::Inherit => parent,
+            mode => mode,
+        };
+
+        let process = match mode {
+            ProcessMode::Inherit | ProcessMode::Pausable => !self.time.paused,
+            ProcessMode::Always => true,
+            ProcessMode::WhenPaused => self.time.paused,
+        };
+        if process {
+            self.time.process_mode = mode;
+            Some(parent)
+        } else {
+            None
+        }
+    }
+    /// Called by `#[derive(Obj)]` after entering an object's process mode.
+    /// Gives the delta its update gets: scaled time for pausable objects, and
+    /// real time for objects that run while paused.
+    #[doc(hidden)]
+    pub fn process_delta(&self) -> Seconds {
+        match self.time.process_mode {
+            ProcessMode::Always | ProcessMode::WhenPaused => self.time.unscaled_delta,
+            ProcessMode::Inherit | ProcessMode::Pausable => self.time.delta,
+        }
+    }
+    #[doc(hidden)]
+    pub fn exit_process_mode(&mut self, parent: ProcessMode) {
+        self.time.process_mode = parent;
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
+
+use slotmap::{new_key_type, SlotMap};
+
+use super::Game;
+use crate::math::Seconds;
+
+new_key_type! { pub struct TimerId; }
+
+enum Callback {
+    None,
+    Once(Box<dyn FnOnce(&mut Game)>),
+    Repeat(Box<dyn FnMut(&mut Game)>),
+}
+
+/// State shared between a timer and its [`Timer`] handle.
+#[derive(Default)]
+struct Shared {
+    fired: Cell<u32>,
+    finished: Cell<bool>,
+}
+
+struct Entry {
+    remaining: Seconds,
+    interval: Option<Seconds>,
+    /// order the timer was created in, to break ties
+    seq: u64,
+    callback: Callback,
+    /// handle owning this timer. the timer is cancelled once it is dropped.
+    owner: Option<Weak<Shared>>,
+}
+
+/// Countdowns and scheduled callbacks, advanced once per frame before the
+/// scene updates. Timers use scaled time, and don't run while the game is
+/// paused. Timers due in the same frame fire in the order they were due, or
+/// the order they were created in if due at the same time.
+pub struct Timers {
+    entries: SlotMap<TimerId, Entry>,
+    next_seq: u64,
+    /// handles that fired last frame, to reset
+    fired: Vec<Weak<Shared>>,
+}
+
+/// Handle to a timer, which is cancelled when the handle is dropped. Store
+/// it in an object to tie the timer to that object.
+pub struct Timer {
+    id: TimerId,
+    shared: Rc<Shared>,
+}
+
+impl Timer {
+    pub fn id(&self) -> TimerId {
+        self.id
+    }
+    /// Whether the timer fired this frame.
+    pub fn just_fired(&self) -> bool {
+        self.shared.fired.get() > 0
+    }
+    /// Number of times the timer fired this frame. Can be more than one for a
+    /// repeating timer shorter than the frame.
+    pub fn fired_count(&self) -> u32 {
+        self.shared.fired.get()
+    }
+    /// Whether a one-shot timer has fired. Never true for repeating timers.
+    pub fn finished(&self) -> bool {
+        self.shared.finished.get()
+    }
+}
+
+impl Timers {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: SlotMap::with_key(),
+            next_seq: 0,
+            fired: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, delay: Seconds, interval: Option<Seconds>, callback: Callback) -> TimerId {
+        if let Some(interval) = interval {
+            assert!(interval > 0., "repeating timer interval must be positive");
+        }
+        self.next_seq += 1;
+        self.entries.insert(Entry {
+            remaining: delay.max(0.),
+            interval,
+            seq: self.next_seq,
+            callback,
+            owner: None,
+        })
+    }
+
+    /// Call `callback` once, `delay` seconds from now.
+    pub fn after(&mut self, delay: Seconds, callback: impl FnOnce(&mut Game) + 'static) -> TimerId {
+        self.insert(delay, None, Callback::Once(Box::new(callback)))
+    }
+    /// Call `callback` every `interval` seconds, starting `interval` seconds
+    /// from now.
+    pub fn every(
+        &mut self,
+        interval: Seconds,
+        callback: impl FnMut(&mut Game) + 'static,
+    ) -> TimerId {
+        self.insert(
+            interval,
+            Some(interval),
+            Callback::Repeat(Box::new(callback)),
+        )
+    }
+
+    /// A timer that fires once after `delay` seconds. Check it with
+    /// [`Timer::just_fired`] or [`Timer::finished`].
+    pub fn once(&mut self, delay: Seconds) -> Timer {
+        let id = self.insert(delay, None, Callback::None);
+        self.owned(id).expect("timer was just created")
+    }
+    /// A timer that fires every `interval` seconds. Check it with
+    /// [`Timer::just_fired`].
+    pub fn repeating(&mut self, interval: Seconds) -> Timer {
+        let id = self.insert(interval, Some(interval), Callback::None);
+        self.owned(id).expect("timer was just created")
+    }
+
+    /// Tie a timer to a handle, so it is cancelled when the handle is dropped.
+    /// Returns `None` if the timer already fired or was cancelled.
+    pub fn owned(&mut self, id: TimerId) -> Option<Timer> {
+        let entry = self.entries.get_mut(id)?;
+        let shared = Rc::new(Shared::default());
+        entry.owner = Some(Rc::downgrade(&shared));
+        Some(Timer { id, shared })
+    }
+
+    /// Stop a timer before it fires. Does nothing if it already finished.
+    pub fn cancel(&mut self, id: TimerId) {
+        self.entries.remove(id);
+    }
+    /// Seconds until the timer next fires.
+    pub fn remaining(&self, id: TimerId) -> Option<Seconds> {
+        self.entries.get(id).map(|entry| entry.remaining)
+    }
+    pub fn is_active(&self, id: TimerId) -> bool {
+        self.entries.contains_key(id)
+    }
+}
+
+impl Game {
+    /// Advance timers by `delta` seconds and fire the ones that are due.
+    pub(crate) fn update_timers(&mut self, delta: Seconds) {
+        for shared in self.timers.fired.drain(..) {
+            if let Some(shared) = shared.upgrade() {
+                shared.fired.set(0);
+            }
+        }
+        if self.time.is_paused() {
+            return;
+        }
+
+        // Drop timers whose handle is gone
+        self.timers
+            .entries
+            .retain(|_, entry| entry.owner.as_ref().is_none_or(|o| o.strong_count() > 0));
+
+        let mut due = Vec::new();
+        for (id, entry) in self.timers.entries.iter_mut() {
+            let mut at = entry.remaining;
+            while at <= delta {
+                due.push((at, entry.seq, id));
+                match entry.interval {
+                    Some(interval) => at += interval,
+                    None => break,
+                }
+            }
+            entry.remaining = (at - delta).max(0.);
+        }
+        due.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        for (_, _, id) in due {
+            // An earlier callback may have cancelled this timer
+            let Some(entry) = self.timers.entries.get_mut(id) else {
+                continue;
+            };
+
+            if let Some(shared) = entry.owner.as_ref().and_then(Weak::upgrade) {
+                shared.fired.set(shared.fired.get() + 1);
+                shared.finished.set(entry.interval.is_none());
+                self.timers.fired.push(Rc::downgrade(&shared));
+            }
+
+            if entry.interval.is_none() {
+                let entry = self.timers.entries.remove(id).expect("timer exists");
+                if let Callback::Once(callback) = entry.callback {
+                    callback(self);
+                }
+            } else if let Callback::Repeat(mut callback) =
+                std::mem::replace(&mut entry.callback, Callback::None)
+            {
+                callback(self);
+                // Put the callback back, unless the timer was cancelled in it
+                if let Some(entry) = self.timers.entries.get_mut(id) {
+                    entry.callback = Callback::Repeat(callback);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::time::Duration;
+
+use image::{imageops::FilterType, RgbaImage};
+pub use miniquad::CursorIcon;
+use miniquad::{
+    conf::{Conf, Icon, Platform},
+    date, window,
+};
+
+use super::{assets::texture::Image, Game, GameBuilder};
+use crate::math::point::Pt2i;
+
+/// Window options set on [`GameBuilder`].
+pub(crate) struct WindowConf {
+    pub(crate) resizable: bool,
+    pub(crate) fullscreen: bool,
+    pub(crate) high_dpi: bool,
+    pub(crate) sample_count: i32,
+    /// `None` leaves it up to the platform
+    pub(crate) vsync: Option<bool>,
+    pub(crate) frame_rate_cap: Option<f32>,
+    pub(crate) icon: Option<Image>,
+}
+
+impl Default for WindowConf {
+    fn default() -> Self {
+        Self {
+            resizable: true,
+            fullscreen: false,
+            high_dpi: false,
+            sample_count: 1,
+            vsync: None,
+            frame_rate_cap: None,
+            icon: None,
+        }
+    }
+}
+
+/// State of the window, which can be changed while the game runs. Headless
+/// games keep track of it without a window.
+pub(crate) struct Window {
+    headless: bool,
+    fullscreen: bool,
+    cursor_shown: bool,
+    cursor_grabbed: bool,
+    focused: bool,
+    quit: bool,
+    quit_blocked: bool,
+    /// clipboard of headless games, which can't use the system's
+    clipboard: Option<String>,
+    /// shortest time between two frames, from the frame rate cap
+    min_frame_time: Option<f64>,
+}
+
+impl Window {
+    pub(crate) fn new(conf: &WindowConf, headless: bool) -> Self {
+        Self {
+            headless,
+            fullscreen: conf.fullscreen,
+            cursor_shown: true,
+            cursor_grabbed: false,
+            focused: true,
+            quit: false,
+            quit_blocked: false,
+            clipboard: None,
+            min_frame_time: conf.frame_rate_cap.map(|fps| 1. / fps as f64),
+        }
+    }
+
+    pub(crate) fn is_headless(&self) -> bool {
+        self.headless
+    }
+}
+
+/// Event sent when the user tries to close the window while quitting is
+/// blocked with [`Game::block_quit`]. Read it with [`Game::events`] to ask
+/// whether to save first, then call [`Game::quit`].
+#[derive(Debug, Clone, Copy)]
+pub struct QuitRequested;
+
+impl GameBuilder {
+    pub(crate) fn conf(&self) -> Conf {
+        let window = &self.window;
+        Conf {
+            window_title: self.window_title.clone().unwrap_or_default(),
+            window_width: self.window_size.x,
+            window_height: self.window_size.y,
+            high_dpi: window.high_dpi,
+            fullscreen: window.fullscreen,
+            sample_count: window.sample_count,
+            window_resizable: window.resizable,
+            icon: window.icon.as_ref().map(icon),
+            platform: Platform {
+                swap_interval: window.vsync.map(|vsync| vsync as i32),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Whether the user can resize the window. Defaults to `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.window.resizable = resizable;
+        self
+    }
+    /// Start in fullscreen.
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.window.fullscreen = fullscreen;
+        self
+    }
+    /// Draw at the full resolution of high-DPI displays.
+    pub fn high_dpi(mut self, high_dpi: bool) -> Self {
+        self.window.high_dpi = high_dpi;
+        self
+    }
+    /// Number of samples used for MSAA. Defaults to 1, which is no MSAA.
+    pub fn sample_count(mut self, samples: i32) -> Self {
+        self.window.sample_count = samples;
+        self
+    }
+    /// Wait for the display's refresh before showing each frame. Not all
+    /// platforms support turning this off.
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.window.vsync = Some(vsync);
+        self
+    }
+    /// Wait between frames so the game never runs faster than `fps`.
+    pub fn frame_rate_cap(mut self, fps: f32) -> Self {
+        assert!(fps > 0., "frame rate cap must be positive");
+        self.window.frame_rate_cap = Some(fps);
+        self
+    }
+    /// Icon shown by the OS, where supported. It is scaled to 16, 32 and 64
+    /// pixels square. Like the title, it can't be changed once the game
+    /// starts, since miniquad 0.4 only takes it when creating the window.
+    pub fn window_icon(mut self, icon: Image) -> Self {
+        self.window.icon = Some(icon);
+        self
+    }
+}
+
+/// Scale an image to each size miniquad needs.
+fn icon(image: &Image) -> Icon {
+    let image = RgbaImage::from_raw(image.width, image.height, image.bytes.clone())
+        .expect("image has width * height pixels");
+    let level = |size| image::imageops::resize(&image, size, size, FilterType::Triangle).into_raw();
+
+    let mut icon = Icon {
+        small: [0; 16 * 16 * 4],
+        medium: [0; 32 * 32 * 4],
+        big: [0; 64 * 64 * 4],
+    };
+    icon.small.copy_from_slice(&level(16));
+    icon.medium.copy_from_slice(&level(32));
+    icon.big.copy_from_slice(&level(64));
+    icon
+}
+
+impl Game {
+    /// Close the window and stop the game, without asking. Every scene exits
+    /// once the current update has finished.
+    pub fn quit(&mut self) {
+        self.window.quit = true;
+        if !self.window.headless {
+            window::order_quit();
+        }
+    }
+    /// Whether [`Game::quit`] was called. Useful for headless games.
+    pub fn has_quit(&self) -> bool {
+        self.window.quit
+    }
+    /// Quit as if the user closed the window, unless quitting is blocked.
+    pub fn request_quit(&mut self) {
+        if self.window.headless {
+            self.quit_requested();
+        } else {
+            window::request_quit();
+        }
+    }
+    /// Stop the user from closing the window, for example while there are
+    /// unsaved changes. A [`QuitRequested`] event is sent instead.
+    pub fn block_quit(&mut self, blocked: bool) {
+        self.window.quit_blocked = blocked;
+    }
+    pub fn is_quit_blocked(&self) -> bool {
+        self.window.quit_blocked
+    }
+    /// Handle a request to quit, returning whether the game quit.
+    pub(crate) fn quit_requested(&mut self) -> bool {
+        if self.window.quit_blocked {
+            self.emit(QuitRequested);
+        } else {
+            self.quit();
+        }
+        self.window.quit
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.window.focused
+    }
+    pub(crate) fn set_focused(&mut self, focused: bool) {
+        if self.window.focused != focused {
+            self.window.focused = focused;
+            if !focused {
+                self.input.release_all();
+            }
+            self.for_each_scene(move |scene, game| scene.focus_children(game, focused));
+        }
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.window.fullscreen = fullscreen;
+        if !self.window.headless {
+            window::set_fullscreen(fullscreen);
+        }
+    }
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.fullscreen
+    }
+
+    /// Ask for a new window size. [`Game::window_size`] changes once the
+    /// window is actually resized, which is right away for headless games.
+    /// A scene that asks from its own update gets
+    /// [`Resize`](crate::obj::Resize) once the update is done.
+    pub fn set_window_size(&mut self, size: impl Into<Pt2i>) {
+        let size = size.into();
+        if self.window.headless {
+            self.resize(size.into());
+        } else {
+            window::set_window_size(size.x as u32, size.y as u32);
+        }
+    }
+
+    pub fn show_cursor(&mut self, shown: bool) {
+        self.window.cursor_shown = shown;
+        if !self.window.headless {
+            window::show_mouse(shown);
+        }
+    }
+    pub fn is_cursor_shown(&self) -> bool {
+        self.window.cursor_shown
+    }
+    /// Keep the cursor inside the window.
+    pub fn grab_cursor(&mut self, grabbed: bool) {
+        self.window.cursor_grabbed = grabbed;
+        if !self.window.headless {
+            window::set_cursor_grab(grabbed);
+        }
+    }
+    pub fn is_cursor_grabbed(&self) -> bool {
+        self.window.cursor_grabbed
+    }
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        if !self.window.headless {
+            window::set_mouse_cursor(icon);
+        }
+    }
+
+    /// Text on the system clipboard, if there is any. Headless games have a
+    /// clipboard of their own.
+    pub fn clipboard(&self) -> Option<String> {
+        if self.window.headless {
+            self.window.clipboard.clone()
+        } else {
+            window::clipboard_get()
+        }
+    }
+    pub fn set_clipboard(&mut self, text: &str) {
+        if self.window.headless {
+            self.window.clipboard = Some(text.to_owned());
+        } else {
+            window::clipboard_set(text);
+        }
+    }
+
+    /// Sleep until the frame rate cap allows the next frame.
+    pub(crate) fn wait_for_frame(&self) {
+        if let Some(min) = self.window.min_frame_time {
+            let elapsed = date::now() - self.last_frame;
+            if elapsed < min {
+                std::thread::sleep(Duration::from_secs_f64(min - elapsed));
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::any::Any;
+
+use miniquad::{
+    window, Backend as Api, PassAction, RenderingBackend, ShaderError, ShaderSource, TextureAccess,
+    TextureParams, TextureSource,
+};
+use slotmap::SlotMap;
+
+use super::{
+    shader::Shader, Bindings, BufferId, BufferSource, BufferType, BufferUsage, ShaderId,
+    ShaderMeta, TextureId,
+};
+use crate::{
+    game::assets::texture::Image,
+    math::{color::Color, point::Pt2},
+    Error, Result,
+};
+
+/// Something that can draw frames. [`GraphicsContext`](super::GraphicsContext)
+/// draws to the window through miniquad by default, but games can also be
+/// run on other backends, see [`headless`](super::headless).
+pub trait Backend: Any {
+    fn create_shader(
+        &mut self,
+        vertex: &str,
+        fragment: &str,
+        metal: &str,
+        meta: ShaderMeta,
+    ) -> Result<ShaderId, ShaderError>;
+    /// Replace the program of a shader, keeping its id. The old program is
+    /// kept if the new one doesn't compile.
+    fn update_shader(
+        &mut self,
+        shader: ShaderId,
+        vertex: &str,
+        fragment: &str,
+        metal: &str,
+        meta: ShaderMeta,
+    ) -> Result<(), ShaderError>;
+    fn create_texture(&mut self, image: &Image) -> TextureId;
+    /// Replace the contents of a texture, which may change its size.
+    fn update_texture(&mut self, texture: TextureId, image: &Image);
+    fn create_buffer(
+        &mut self,
+        kind: BufferType,
+        usage: BufferUsage,
+        data: BufferSource,
+    ) -> BufferId;
+    fn update_buffer(&mut self, buffer: BufferId, data: BufferSource);
+
+    /// Size in pixels of the surface drawn to changed.
+    fn resize(&mut self, _size: Pt2) {}
+
+    fn begin_frame(&mut self, clear_color: Color);
+    fn draw(&mut self, shader: ShaderId, bindings: &Bindings, num_indices: i32);
+    fn end_frame(&mut self);
+}
+
+/// Draws to the window with miniquad.
+pub(crate) struct MiniquadBackend {
+    ctx: Box<dyn RenderingBackend>,
+    shaders: SlotMap<ShaderId, Shader>,
+    buffers: SlotMap<BufferId, (miniquad::BufferId, BufferType)>,
+    textures: SlotMap<TextureId, miniquad::TextureId>,
+}
+
+impl MiniquadBackend {
+    pub(crate) fn new() -> Result<Self> {
+        let ctx = window::new_rendering_backend();
+        if ctx.info().backend == Api::Metal {
+            return Err(Error::UnsupportedGraphics("metal"));
+        }
+
+        Ok(Self {
+            ctx,
+            shaders: SlotMap::with_key(),
+            buffers: SlotMap::with_key(),
+            textures: SlotMap::with_key(),
+        })
+    }
+
+    fn new_shader(
+        &mut self,
+        vertex: &str,
+        fragment: &str,
+        metal: &str,
+        meta: ShaderMeta,
+    ) -> Result<Shader, ShaderError> {
+        let program = self.ctx.new_shader(
+            match self.ctx.info().backend {
+                Api::OpenGl => ShaderSource::Glsl { vertex, fragment },
+                Api::Metal => ShaderSource::Msl { program: metal },
+            },
+            meta,
+        )?;
+        Ok(Shader::new(&mut self.ctx, program))
+    }
+
+    fn new_texture(&mut self, image: &Image) -> miniquad::TextureId {
+        self.ctx.new_texture(
+            TextureAccess::Static,
+            TextureSource::Bytes(&image.bytes),
+            TextureParams {
+                width: image.width,
+                height: image.height,
+                min_filter: miniquad::FilterMode::Nearest,
+                mag_filter: miniquad::FilterMode::Nearest,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// miniquad reads the index type from the element size of the source, so
+    /// index data has to be passed as a slice of the right integer type.
+    fn with_source<R>(
+        kind: BufferType,
+        data: BufferSource,
+        f: impl FnOnce(miniquad::BufferSource) -> R,
+    ) -> R {
+        match data {
+            BufferSource::Empty { size, element_size } => {
+                f(miniquad::BufferSource::Empty { size, element_size })
+            }
+            BufferSource::Slice { bytes, .. } if kind == BufferType::VertexBuffer => {
+                f(miniquad::BufferSource::slice(bytes))
+            }
+            BufferSource::Slice {
+                bytes,
+                element_size: 2,
+            } => {
+                let indices: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|b| u16::from_ne_bytes([b[0], b[1]]))
+                    .collect();
+                f(miniquad::BufferSource::slice(&indices))
+            }
+            BufferSource::Slice {
+                bytes,
+                element_size: 4,
+            } => {
+                let indices: Vec<u32> = bytes
+                    .chunks_exact(4)
+                    .map(|b| u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                f(miniquad::BufferSource::slice(&indices))
+            }
+            BufferSource::Slice { bytes, .. } => f(miniquad::BufferSource::slice(bytes)),
+        }
+    }
+}
+
+impl Backend for MiniquadBackend {
+    fn create_shader(
+        &mut self,
+        vertex: &str,
+        fragment: &str,
+        metal: &str,
+        meta: ShaderMeta,
+    ) -> Result<ShaderId, ShaderError> {
+        let shader = self.new_shader(vertex, fragment, metal, meta)?;
+        Ok(self.shaders.insert(shader))
+    }
+
+    fn update_shader(
+        &mut self,
+        shader: ShaderId,
+        vertex: &str,
+        fragment: &str,
+        metal: &str,
+        meta: ShaderMeta,
+    ) -> Result<(), ShaderError> {
+        let new = self.new_shader(vertex, fragment, metal, meta)?;
+        let old = std::mem::replace(&mut self.shaders[shader], new);
+        self.ctx.delete_pipeline(old.pipeline);
+        self.ctx.delete_shader(old.program);
+        Ok(())
+    }
+
+    fn create_texture(&mut self, image: &Image) -> TextureId {
+        let texture = self.new_texture(image);
+        self.textures.insert(texture)
+    }
+
+    fn update_texture(&mut self, texture: TextureId, image: &Image) {
+        let old = self.textures[texture];
+        if self.ctx.texture_size(old) == (image.width, image.height) {
+            self.ctx.texture_update(old, &image.bytes);
+        } else {
+            self.textures[texture] = self.new_texture(image);
+            self.ctx.delete_texture(old);
+        }
+    }
+
+    fn create_buffer(
+        &mut self,
+        kind: BufferType,
+        usage: BufferUsage,
+        data: BufferSource,
+    ) -> BufferId {
+        let buffer = Self::with_source(kind, data, |data| self.ctx.new_buffer(kind, usage, data));
+        self.buffers.insert((buffer, kind))
+    }
+
+    fn update_buffer(&mut self, buffer: BufferId, data: BufferSource) {
+        let (buffer, kind) = self.buffers[buffer];
+        Self::with_source(kind, data, |data| self.ctx.buffer_update(buffer, data))
+    }
+
+    fn begin_frame(&mut self, clear_color: Color) {
+        let color: [f32; 4] = clear_color.into();
+        self.ctx.begin_default_pass(PassAction::clear_color(
+            color[0], color[1], color[2], color[3],
+        ))
+    }
+
+    fn draw(&mut self, shader: ShaderId, bindings: &Bindings, num_indices: i32) {
+        self.ctx.apply_pipeline(&self.shaders[shader].pipeline);
+        self.ctx.apply_bindings(&miniquad::Bindings {
+            vertex_buffers: bindings
+                .vertex_buffers
+                .iter()
+                .map(|&buffer| self.buffers[buffer].0)
+                .collect(),
+            index_buffer: self.buffers[bindings.index_buffer].0,
+            images: bindings
+                .images
+                .iter()
+                .map(|&texture| self.textures[texture])
+                .collect(),
+        });
+        self.ctx.draw(0, num_indices, 1);
+    }
+
+    fn end_frame(&mut self) {
+        self.ctx.end_render_pass();
+        self.ctx.commit_frame();
+    }
+}
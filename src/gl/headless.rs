@@ -0,0 +1,196 @@
+//! Running games without a window or GPU, for automated tests.
+//!
+//! [`GameBuilder::headless`](crate::game::GameBuilder::headless) builds a
+//! [`Game`](crate::game::Game) that draws to a [`RecordingBackend`]. Step it
+//! with [`Game::step`](crate::game::Game::step), feed it input by calling its
+//! [`EventHandler`](miniquad::EventHandler) methods, then inspect the scene
+//! and the recorded draw calls.
+
+use miniquad::ShaderError;
+use slotmap::SlotMap;
+
+use super::{
+    backend::Backend, vertex::Vertex, Bindings, BufferId, BufferSource, BufferType, BufferUsage,
+    ShaderId, ShaderMeta, TextureId,
+};
+use crate::{
+    game::assets::texture::Image,
+    math::{color::Color, point::pt2},
+};
+
+/// Contents of a buffer kept in memory.
+pub(crate) struct CpuBuffer {
+    bytes: Vec<u8>,
+    element_size: usize,
+}
+
+impl CpuBuffer {
+    pub(crate) fn new(data: BufferSource) -> Self {
+        match data {
+            BufferSource::Slice {
+                bytes,
+                element_size,
+            } => Self {
+                bytes: bytes.to_vec(),
+                element_size,
+            },
+            BufferSource::Empty { size, element_size } => Self {
+                bytes: vec![0; size],
+                element_size,
+            },
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: BufferSource) {
+        *self = Self::new(data);
+    }
+
+    pub(crate) fn vertices(&self) -> Vec<Vertex> {
+        self.bytes
+            .chunks_exact(size_of::<Vertex>())
+            .map(|v| {
+                let f = |i: usize| f32::from_ne_bytes([v[i], v[i + 1], v[i + 2], v[i + 3]]);
+                Vertex::new(pt2(f(0), f(4)), pt2(f(8), f(12)))
+            })
+            .collect()
+    }
+
+    pub(crate) fn indices(&self) -> Vec<u32> {
+        match self.element_size {
+            2 => self
+                .bytes
+                .chunks_exact(2)
+                .map(|b| u16::from_ne_bytes([b[0], b[1]]) as u32)
+                .collect(),
+            4 => self
+                .bytes
+                .chunks_exact(4)
+                .map(|b| u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            _ => self.bytes.iter().map(|&b| b as u32).collect(),
+        }
+    }
+}
+
+/// One recorded call to [`GraphicsContext::draw`](super::GraphicsContext::draw).
+#[derive(Debug)]
+pub struct DrawCall {
+    pub shader: ShaderId,
+    pub images: Vec<TextureId>,
+    /// Vertices in clip space, in the order they are drawn.
+    pub vertices: Vec<Vertex>,
+}
+
+#[derive(Debug)]
+pub struct Frame {
+    pub clear_color: Color,
+    pub draw_calls: Vec<DrawCall>,
+}
+
+/// A backend that draws nothing, and instead records every draw call.
+#[derive(Default)]
+pub struct RecordingBackend {
+    /// vertex and fragment source of each shader
+    shaders: SlotMap<ShaderId, (String, String)>,
+    textures: SlotMap<TextureId, (u32, u32)>,
+    buffers: SlotMap<BufferId, CpuBuffer>,
+
+    frames: Vec<Frame>,
+}
+
+impl RecordingBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every frame drawn so far, oldest first.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+    pub fn last_frame(&self) -> Option<&Frame> {
+        self.frames.last()
+    }
+    /// Vertex and fragment source of a shader.
+    pub fn shader_source(&self, shader: ShaderId) -> Option<(&str, &str)> {
+        let (vertex, fragment) = self.shaders.get(shader)?;
+        Some((vertex, fragment))
+    }
+    /// Size in pixels of a texture.
+    pub fn texture_size(&self, texture: TextureId) -> Option<(u32, u32)> {
+        self.textures.get(texture).copied()
+    }
+}
+
+impl Backend for RecordingBackend {
+    fn create_shader(
+        &mut self,
+        vertex: &str,
+        fragment: &str,
+        _metal: &str,
+        _meta: ShaderMeta,
+    ) -> Result<ShaderId, ShaderError> {
+        Ok(self
+            .shaders
+            .insert((vertex.to_owned(), fragment.to_owned())))
+    }
+
+    fn update_shader(
+        &mut self,
+        shader: ShaderId,
+        vertex: &str,
+        fragment: &str,
+        _metal: &str,
+        _meta: ShaderMeta,
+    ) -> Result<(), ShaderError> {
+        self.shaders[shader] = (vertex.to_owned(), fragment.to_owned());
+        Ok(())
+    }
+
+    fn create_texture(&mut self, image: &Image) -> TextureId {
+        self.textures.insert((image.width, image.height))
+    }
+
+    fn update_texture(&mut self, texture: TextureId, image: &Image) {
+        self.textures[texture] = (image.width, image.height);
+    }
+
+    fn create_buffer(
+        &mut self,
+        _kind: BufferType,
+        _usage: BufferUsage,
+        data: BufferSource,
+    ) -> BufferId {
+        self.buffers.insert(CpuBuffer::new(data))
+    }
+
+    fn update_buffer(&mut self, buffer: BufferId, data: BufferSource) {
+        self.buffers[buffer].update(data)
+    }
+
+    fn begin_frame(&mut self, clear_color: Color) {
+        self.frames.push(Frame {
+            clear_color,
+            draw_calls: Vec::new(),
+        })
+    }
+
+    fn draw(&mut self, shader: ShaderId, bindings: &Bindings, num_indices: i32) {
+        let vertices = self.buffers[bindings.vertex_buffers[0]].vertices();
+        let vertices = self.buffers[bindings.index_buffer]
+            .indices()
+            .into_iter()
+            .take(num_indices as usize)
+            .map(|i| vertices[i as usize])
+            .collect();
+
+        if let Some(frame) = self.frames.last_mut() {
+            frame.draw_calls.push(DrawCall {
+                shader,
+                images: bindings.images.clone(),
+                vertices,
+            })
+        }
+    }
+
+    fn end_frame(&mut self) {}
+}
@@ -1,11 +1,7 @@
-use std::error::Error;
+use std::any::Any;
 
-use miniquad::{
-    window, Backend, Bindings, BufferId, BufferSource, BufferType, BufferUsage, PassAction,
-    RenderingBackend, ShaderMeta, ShaderSource, TextureAccess, TextureId, TextureParams,
-    TextureSource,
-};
-use slotmap::{new_key_type, SlotMap};
+pub use miniquad::{BufferType, BufferUsage, ShaderMeta};
+use slotmap::{new_key_type, SecondaryMap};
 use vertex::Vertex;
 
 use crate::{
@@ -13,128 +9,192 @@ use crate::{
     math::{
         color::Color,
         matrix::Matrix,
-        point::{pt2, Pt2},
+        point::{pt2, Pt2, Pt2i},
         transform::Transform,
     },
+    Result,
 };
-use shader::Shader;
+use backend::Backend;
 
+pub mod backend;
+pub mod headless;
 mod shader;
+pub mod software;
 pub mod vertex;
 
-new_key_type! { pub struct ShaderId; }
+new_key_type! {
+    pub struct ShaderId;
+    pub struct BufferId;
+    pub struct TextureId;
+}
+
+/// Buffers and textures used by a draw call.
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    pub vertex_buffers: Vec<BufferId>,
+    pub index_buffer: BufferId,
+    pub images: Vec<TextureId>,
+}
+
+/// Data to fill a buffer with.
+#[derive(Clone, Copy)]
+pub enum BufferSource<'a> {
+    Slice {
+        bytes: &'a [u8],
+        element_size: usize,
+    },
+    Empty {
+        size: usize,
+        element_size: usize,
+    },
+}
+
+impl<'a> BufferSource<'a> {
+    /// Empty buffer with room for `size` elements of `T`.
+    pub fn empty<T>(size: usize) -> Self {
+        let element_size = size_of::<T>();
+        BufferSource::Empty {
+            size: size * element_size,
+            element_size,
+        }
+    }
+
+    /// Buffer filled with `data`. `T` should be a plain data type such as
+    /// [`Vertex`] or an integer index.
+    pub fn slice<T: Copy>(data: &'a [T]) -> Self {
+        // SAFETY: the bytes are only read, and live as long as `data`.
+        let bytes =
+            unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) };
+        BufferSource::Slice {
+            bytes,
+            element_size: size_of::<T>(),
+        }
+    }
+}
 
 pub struct GraphicsContext {
-    ctx: Box<dyn RenderingBackend>,
-    shaders: SlotMap<ShaderId, Shader>,
+    backend: Box<dyn Backend>,
     default_shader: ShaderId,
+    /// kept here so drawing can follow textures that change size
+    texture_sizes: SecondaryMap<TextureId, Pt2i>,
 
     indices_square: BufferId,
     viewport_transform: Transform,
+    alpha: f32,
 }
 
 impl GraphicsContext {
-    pub(crate) fn new() -> Result<Self, Box<dyn Error>> {
-        let mut ctx = window::new_rendering_backend();
-
-        let mut shaders = SlotMap::with_key();
-        let default_shader = ctx.new_shader(
-            match ctx.info().backend {
-                Backend::OpenGl => ShaderSource::Glsl {
-                    vertex: shader::default::VERTEX,
-                    fragment: shader::default::FRAGMENT,
-                },
-                Backend::Metal => panic!("metal is not supported yet."),
-            },
+    /// Make a context that draws to the window. Must be called after the
+    /// window has been created.
+    pub(crate) fn new() -> Result<Self> {
+        let size = miniquad::window::screen_size();
+        Self::with_backend(backend::MiniquadBackend::new()?, size.into())
+    }
+
+    pub(crate) fn with_backend(backend: impl Backend, size: Pt2) -> Result<Self> {
+        let mut backend: Box<dyn Backend> = Box::new(backend);
+
+        let default_shader = backend.create_shader(
+            shader::default::VERTEX,
+            shader::default::FRAGMENT,
+            "",
             shader::default::meta(),
         )?;
-        let default_shader = shaders.insert(Shader::new(&mut ctx, default_shader));
 
-        let indices_square = ctx.new_buffer(
+        let indices_square = backend.create_buffer(
             BufferType::IndexBuffer,
             BufferUsage::Immutable,
             BufferSource::slice(&[0, 1, 2, 0, 2, 3]),
         );
 
-        let size = window::screen_size();
-        let viewport_transform = Transform::new(
-            Matrix::new([[1. / size.0, 0.], [0., -1. / size.1]]),
-            pt2(-1.0, 1.0),
-            Pt2::ZERO,
-        );
-
-        Ok(Self {
+        let mut ctx = Self {
+            backend,
             default_shader,
-            shaders,
+            texture_sizes: SecondaryMap::new(),
             indices_square,
-            ctx,
-            viewport_transform,
-        })
+            viewport_transform: Transform::IDENTITY,
+            alpha: 1.,
+        };
+        ctx.update_viewport_transform(size);
+        Ok(ctx)
     }
 
+    /// Compile a GLSL shader. It gets the same inputs as the default shader:
+    /// `in_pos` and `in_uv` attributes, and a `tex` texture.
     pub(crate) fn create_shader(
         &mut self,
         vertex: &str,
         fragment: &str,
-        metal: &str,
-        meta: ShaderMeta,
     ) -> Result<ShaderId, miniquad::ShaderError> {
-        let shader = self.ctx.new_shader(
-            match self.ctx.info().backend {
-                Backend::OpenGl => ShaderSource::Glsl { vertex, fragment },
-                Backend::Metal => ShaderSource::Msl { program: metal },
-            },
-            meta,
-        )?;
-        Ok(self.shaders.insert(Shader::new(&mut self.ctx, shader)))
+        self.backend
+            .create_shader(vertex, fragment, "", shader::default::meta())
+    }
+    #[cfg_attr(not(feature = "hot-reload"), expect(dead_code))]
+    pub(crate) fn update_shader(
+        &mut self,
+        shader: ShaderId,
+        vertex: &str,
+        fragment: &str,
+    ) -> Result<(), miniquad::ShaderError> {
+        self.backend
+            .update_shader(shader, vertex, fragment, "", shader::default::meta())
     }
     pub(crate) fn create_texture(&mut self, image: &Image) -> TextureId {
-        self.ctx.new_texture(
-            TextureAccess::Static,
-            TextureSource::Bytes(&image.bytes),
-            TextureParams {
-                width: image.width,
-                height: image.height,
-                mag_filter: miniquad::FilterMode::Nearest,
-                ..Default::default()
-            },
-        )
+        let texture = self.backend.create_texture(image);
+        self.texture_sizes.insert(texture, image.size());
+        texture
+    }
+    #[cfg_attr(not(feature = "hot-reload"), expect(dead_code))]
+    pub(crate) fn update_texture(&mut self, texture: TextureId, image: &Image) {
+        self.backend.update_texture(texture, image);
+        self.texture_sizes.insert(texture, image.size());
+    }
+    /// Current size in pixels of a texture, which changes if it is hot
+    /// reloaded from a different sized image.
+    pub fn texture_size(&self, texture: TextureId) -> Option<Pt2i> {
+        self.texture_sizes.get(texture).copied()
     }
 
     pub fn create_vertex_buffer(&mut self, size: usize) -> BufferId {
-        self.ctx.new_buffer(
+        self.backend.create_buffer(
             BufferType::VertexBuffer,
             BufferUsage::Stream,
             BufferSource::empty::<Vertex>(size),
         )
     }
     pub fn create_index_buffer(&mut self, size: usize) -> BufferId {
-        self.ctx.new_buffer(
+        self.backend.create_buffer(
             BufferType::IndexBuffer,
             BufferUsage::Stream,
             BufferSource::empty::<usize>(size),
         )
     }
     pub fn update_buffer(&mut self, buffer: BufferId, data: BufferSource) {
-        self.ctx.buffer_update(buffer, data)
+        self.backend.update_buffer(buffer, data)
     }
 
     pub(crate) fn start_frame(&mut self, color: Color) {
-        let color: [f32; 4] = color.into();
-        self.ctx.begin_default_pass(PassAction::clear_color(
-            color[0], color[1], color[2], color[3],
-        ))
+        self.backend.begin_frame(color)
     }
     pub(crate) fn finish(&mut self) {
-        self.ctx.end_render_pass();
-        self.ctx.commit_frame();
+        self.backend.end_frame()
     }
 
     pub fn draw(&mut self, bindings: &Bindings, num_indices: i32) {
-        self.ctx
-            .apply_pipeline(&self.shaders[self.default_shader].pipeline);
-        self.ctx.apply_bindings(bindings);
-        self.ctx.draw(0, num_indices, 1);
+        self.backend
+            .draw(self.default_shader, bindings, num_indices);
+    }
+    /// Draw with a shader loaded as an
+    /// [`assets::shader::Shader`](crate::game::assets::shader::Shader) instead
+    /// of the default one.
+    pub fn draw_with_shader(&mut self, shader: ShaderId, bindings: &Bindings, num_indices: i32) {
+        self.backend.draw(shader, bindings, num_indices);
+    }
+
+    /// The backend this context draws with, if it is a `B`. Useful to read
+    /// back what a [`headless`] game drew.
+    pub fn backend<B: Backend>(&self) -> Option<&B> {
+        (&*self.backend as &dyn Any).downcast_ref()
     }
 
     pub fn indices_square(&self) -> BufferId {
@@ -143,11 +203,22 @@ impl GraphicsContext {
     pub fn viewport_transform(&self) -> Transform {
         self.viewport_transform
     }
+    /// How far the current frame is between the last fixed update and the
+    /// next one, from 0 to 1. Use this to interpolate positions that are
+    /// moved in [`FixedUpdate`](crate::obj::FixedUpdate). Always 1 without a
+    /// fixed timestep.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+    pub(crate) fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha;
+    }
     pub(crate) fn update_viewport_transform(&mut self, size: Pt2) {
         self.viewport_transform = Transform::new(
             Matrix::new([[2. / size.x, 0.], [0., -2. / size.y]]),
             pt2(-1.0, 1.0),
             Pt2::ZERO,
         );
+        self.backend.resize(size);
     }
 }
@@ -1,5 +1,6 @@
 use miniquad::{
-    BufferLayout, Pipeline, PipelineParams, RenderingBackend, VertexAttribute, VertexFormat,
+    BlendFactor, BlendState, BlendValue, BufferLayout, Equation, Pipeline, PipelineParams,
+    RenderingBackend, VertexAttribute, VertexFormat,
 };
 
 pub mod default {
@@ -18,6 +19,7 @@ pub mod default {
 
 pub struct Shader {
     pub(super) pipeline: Pipeline,
+    pub(super) program: miniquad::ShaderId,
 }
 
 impl Shader {
@@ -32,9 +34,24 @@ impl Shader {
                 VertexAttribute::new("in_uv", VertexFormat::Float2),
             ],
             quad_shader,
-            PipelineParams::default(),
+            PipelineParams {
+                color_blend: Some(BlendState::new(
+                    Equation::Add,
+                    BlendFactor::Value(BlendValue::SourceAlpha),
+                    BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+                )),
+                alpha_blend: Some(BlendState::new(
+                    Equation::Add,
+                    BlendFactor::One,
+                    BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+                )),
+                ..Default::default()
+            },
         );
 
-        Self { pipeline }
+        Self {
+            pipeline,
+            program: quad_shader,
+        }
     }
 }
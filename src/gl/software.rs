@@ -0,0 +1,337 @@
+//! A reference rasterizer that draws on the CPU, for checking what frames
+//! look like on machines without a GPU.
+//!
+//! [`SoftwareBackend`] runs the same pipeline as the default shader: textured
+//! triangles with nearest sampling and alpha blending. Pass it to
+//! [`GameBuilder::headless_with`](crate::game::GameBuilder::headless_with),
+//! then check [`SoftwareBackend::frame`] against a PNG with
+//! [`compare_golden`].
+
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+};
+
+use image::ColorType;
+use miniquad::ShaderError;
+use slotmap::SlotMap;
+
+use super::{
+    backend::Backend, headless::CpuBuffer, vertex::Vertex, Bindings, BufferId, BufferSource,
+    BufferType, BufferUsage, ShaderId, ShaderMeta, TextureId,
+};
+use crate::{
+    game::assets::{texture::Image, Asset},
+    math::{color::Color, point::Pt2},
+};
+
+/// Draws into an [`Image`] on the CPU.
+pub struct SoftwareBackend {
+    shaders: SlotMap<ShaderId, ()>,
+    textures: SlotMap<TextureId, Image>,
+    buffers: SlotMap<BufferId, CpuBuffer>,
+
+    frame: Image,
+}
+
+impl SoftwareBackend {
+    pub fn new() -> Self {
+        Self {
+            shaders: SlotMap::with_key(),
+            textures: SlotMap::with_key(),
+            buffers: SlotMap::with_key(),
+            frame: Image::from_color(0, 0, Color::TRANSPARENT),
+        }
+    }
+
+    /// The last frame drawn.
+    pub fn frame(&self) -> &Image {
+        &self.frame
+    }
+
+    fn to_pixels(&self, vertex: Vertex) -> Vertex {
+        let w = self.frame.width as f32;
+        let h = self.frame.height as f32;
+        Vertex::new(
+            Pt2::new((vertex.pos.x + 1.) / 2. * w, (1. - vertex.pos.y) / 2. * h),
+            vertex.uv,
+        )
+    }
+
+    fn draw_triangle(&mut self, texture: Option<TextureId>, mut tri: [Vertex; 3]) {
+        let mut area = edge(tri[0].pos, tri[1].pos, tri[2].pos);
+        if area == 0. {
+            return;
+        }
+        // Make the winding consistent so the same fill rule works for both.
+        if area < 0. {
+            tri.swap(1, 2);
+            area = -area;
+        }
+
+        let min_x = tri.iter().map(|v| v.pos.x).fold(f32::MAX, f32::min);
+        let max_x = tri.iter().map(|v| v.pos.x).fold(f32::MIN, f32::max);
+        let min_y = tri.iter().map(|v| v.pos.y).fold(f32::MAX, f32::min);
+        let max_y = tri.iter().map(|v| v.pos.y).fold(f32::MIN, f32::max);
+
+        let x0 = min_x.floor().max(0.) as u32;
+        let y0 = min_y.floor().max(0.) as u32;
+        let x1 = (max_x.ceil().max(0.) as u32).min(self.frame.width);
+        let y1 = (max_y.ceil().max(0.) as u32).min(self.frame.height);
+
+        let edges = [(1, 2), (2, 0), (0, 1)];
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = Pt2::new(x as f32 + 0.5, y as f32 + 0.5);
+
+                let mut weights = [0.; 3];
+                let mut inside = true;
+                for (i, &(a, b)) in edges.iter().enumerate() {
+                    let w = edge(tri[a].pos, tri[b].pos, p);
+                    if w < 0. || (w == 0. && !is_top_left(tri[a].pos, tri[b].pos)) {
+                        inside = false;
+                        break;
+                    }
+                    weights[i] = w / area;
+                }
+                if !inside {
+                    continue;
+                }
+
+                let uv = tri[0].uv * weights[0] + tri[1].uv * weights[1] + tri[2].uv * weights[2];
+                let color = match texture.and_then(|t| self.textures.get(t)) {
+                    Some(image) => sample_nearest(image, uv),
+                    None => Color::WHITE,
+                };
+                blend(&mut self.frame, x, y, color);
+            }
+        }
+    }
+}
+
+impl Default for SoftwareBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for SoftwareBackend {
+    /// Only the default pipeline is supported, so shader sources are ignored.
+    fn create_shader(
+        &mut self,
+        _vertex: &str,
+        _fragment: &str,
+        _metal: &str,
+        _meta: ShaderMeta,
+    ) -> Result<ShaderId, ShaderError> {
+        Ok(self.shaders.insert(()))
+    }
+
+    fn update_shader(
+        &mut self,
+        _shader: ShaderId,
+        _vertex: &str,
+        _fragment: &str,
+        _metal: &str,
+        _meta: ShaderMeta,
+    ) -> Result<(), ShaderError> {
+        Ok(())
+    }
+
+    fn create_texture(&mut self, image: &Image) -> TextureId {
+        self.textures.insert(image.clone())
+    }
+
+    fn update_texture(&mut self, texture: TextureId, image: &Image) {
+        self.textures[texture] = image.clone();
+    }
+
+    fn create_buffer(
+        &mut self,
+        _kind: BufferType,
+        _usage: BufferUsage,
+        data: BufferSource,
+    ) -> BufferId {
+        self.buffers.insert(CpuBuffer::new(data))
+    }
+
+    fn update_buffer(&mut self, buffer: BufferId, data: BufferSource) {
+        self.buffers[buffer].update(data)
+    }
+
+    fn resize(&mut self, size: Pt2) {
+        self.frame = Image::from_color(size.x as u32, size.y as u32, Color::TRANSPARENT);
+    }
+
+    fn begin_frame(&mut self, clear_color: Color) {
+        self.frame = Image::from_color(self.frame.width, self.frame.height, clear_color);
+    }
+
+    fn draw(&mut self, _shader: ShaderId, bindings: &Bindings, num_indices: i32) {
+        let vertices = self.buffers[bindings.vertex_buffers[0]].vertices();
+        let indices = self.buffers[bindings.index_buffer].indices();
+        let texture = bindings.images.first().copied();
+
+        for tri in indices[..(num_indices as usize).min(indices.len())].chunks_exact(3) {
+            let tri = [0, 1, 2].map(|i| self.to_pixels(vertices[tri[i] as usize]));
+            self.draw_triangle(texture, tri);
+        }
+    }
+
+    fn end_frame(&mut self) {}
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`.
+fn edge(a: Pt2, b: Pt2, p: Pt2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Pixels exactly on an edge are only filled if it is a top or left edge, so
+/// triangles sharing an edge don't blend over each other twice.
+fn is_top_left(a: Pt2, b: Pt2) -> bool {
+    (a.y == b.y && b.x < a.x) || b.y > a.y
+}
+
+fn sample_nearest(image: &Image, uv: Pt2) -> Color {
+    if image.width == 0 || image.height == 0 {
+        return Color::TRANSPARENT;
+    }
+    let x = ((uv.x * image.width as f32).floor().max(0.) as u32).min(image.width - 1);
+    let y = ((uv.y * image.height as f32).floor().max(0.) as u32).min(image.height - 1);
+    let i = ((y * image.width + x) * 4) as usize;
+    [
+        image.bytes[i],
+        image.bytes[i + 1],
+        image.bytes[i + 2],
+        image.bytes[i + 3],
+    ]
+    .into()
+}
+
+/// Source alpha over destination, like the default pipeline's blend state.
+fn blend(frame: &mut Image, x: u32, y: u32, src: Color) {
+    let i = ((y * frame.width + x) * 4) as usize;
+    let dst = &mut frame.bytes[i..i + 4];
+
+    let a = src.a as f32 / 255.;
+    let mix = |s: u8, d: u8| (s as f32 * a + d as f32 * (1. - a)).round() as u8;
+    dst[0] = mix(src.r, dst[0]);
+    dst[1] = mix(src.g, dst[1]);
+    dst[2] = mix(src.b, dst[2]);
+    dst[3] = (src.a as f32 + dst[3] as f32 * (1. - a)).round() as u8;
+}
+
+/// Environment variable that makes [`compare_golden`] write the frame as the
+/// new golden image instead of comparing.
+pub const UPDATE_GOLDEN_VAR: &str = "MOZART_UPDATE_GOLDEN";
+
+#[derive(Debug)]
+pub enum GoldenError {
+    /// There is no golden image at this path. Run with
+    /// [`UPDATE_GOLDEN_VAR`] set to create it.
+    Missing(PathBuf),
+    /// The golden image could not be read or the frame could not be written.
+    Io(PathBuf, Box<dyn std::error::Error>),
+    SizeMismatch {
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+    /// Some pixels differ by more than the tolerance. Differing pixels are
+    /// marked in red in the image written to `diff`.
+    Mismatch { pixels: usize, diff: PathBuf },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Missing(path) => write!(
+                f,
+                "golden image {} does not exist, set {UPDATE_GOLDEN_VAR}=1 to create it",
+                path.display()
+            ),
+            GoldenError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            GoldenError::SizeMismatch { expected, found } => write!(
+                f,
+                "frame is {}x{}, but golden image is {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            GoldenError::Mismatch { pixels, diff } => write!(
+                f,
+                "{pixels} pixels differ from the golden image, see {}",
+                diff.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+/// Compare `frame` with the PNG at `path`. Pixels match if no channel differs
+/// by more than `tolerance`. On a mismatch, a diff image is written next to
+/// the golden file with a `.diff.png` extension.
+pub fn compare_golden(
+    frame: &Image,
+    path: impl AsRef<Path>,
+    tolerance: u8,
+) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+
+    if env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        return save_png(frame, path);
+    }
+
+    let data = std::fs::read(path).map_err(|_| GoldenError::Missing(path.to_owned()))?;
+    let golden = Image::load(&data).map_err(|err| GoldenError::Io(path.to_owned(), err))?;
+
+    if golden.size() != frame.size() {
+        return Err(GoldenError::SizeMismatch {
+            expected: (golden.width, golden.height),
+            found: (frame.width, frame.height),
+        });
+    }
+
+    let mut diff = Image::from_color(frame.width, frame.height, Color::BLACK);
+    let mut pixels = 0;
+    for ((expected, found), out) in golden
+        .bytes
+        .chunks_exact(4)
+        .zip(frame.bytes.chunks_exact(4))
+        .zip(diff.bytes.chunks_exact_mut(4))
+    {
+        let differs = expected
+            .iter()
+            .zip(found)
+            .any(|(&e, &f)| e.abs_diff(f) > tolerance);
+        if differs {
+            pixels += 1;
+            out.copy_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Keep a faded copy of the frame for context.
+            for c in 0..3 {
+                out[c] = found[c] / 4;
+            }
+        }
+    }
+
+    if pixels == 0 {
+        return Ok(());
+    }
+
+    let diff_path = path.with_extension("diff.png");
+    save_png(&diff, &diff_path)?;
+    Err(GoldenError::Mismatch {
+        pixels,
+        diff: diff_path,
+    })
+}
+
+fn save_png(image: &Image, path: &Path) -> Result<(), GoldenError> {
+    image::save_buffer(
+        path,
+        &image.bytes,
+        image.width,
+        image.height,
+        ColorType::Rgba8,
+    )
+    .map_err(|err| GoldenError::Io(path.to_owned(), err.into()))
+}
@@ -1,7 +1,7 @@
 use crate::math::point::Pt2;
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Vertex {
     pub pos: Pt2,
     pub uv: Pt2,
@@ -1,4 +1,7 @@
+pub mod error;
 pub mod game;
 pub mod gl;
 pub mod math;
 pub mod obj;
+
+pub use error::{Error, Result};
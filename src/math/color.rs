@@ -1,5 +1,5 @@
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -38,63 +38,82 @@ impl Color {
     }
 }
 
-impl Into<Color> for [u8; 4] {
-    fn into(self) -> Color {
+impl From<[u8; 4]> for Color {
+    fn from(value: [u8; 4]) -> Self {
         Color {
-            r: self[0],
-            g: self[1],
-            b: self[2],
-            a: self[3],
+            r: value[0],
+            g: value[1],
+            b: value[2],
+            a: value[3],
         }
     }
 }
 
-impl Into<[u8; 4]> for Color {
-    fn into(self) -> [u8; 4] {
-        [self.r, self.g, self.b, self.a]
+impl From<Color> for [u8; 4] {
+    fn from(value: Color) -> Self {
+        [value.r, value.g, value.b, value.a]
     }
 }
 
-impl Into<Color> for [f32; 4] {
-    fn into(self) -> Color {
+impl From<[f32; 4]> for Color {
+    fn from(value: [f32; 4]) -> Self {
         Color {
-            r: (self[0] * 255.).round().abs() as u8,
-            g: (self[0] * 255.).round().abs() as u8,
-            b: (self[0] * 255.).round().abs() as u8,
-            a: (self[0] * 255.).round().abs() as u8,
+            r: (value[0] * 255.).round().abs() as u8,
+            g: (value[1] * 255.).round().abs() as u8,
+            b: (value[2] * 255.).round().abs() as u8,
+            a: (value[3] * 255.).round().abs() as u8,
         }
     }
 }
 
-impl Into<[f32; 4]> for Color {
-    fn into(self) -> [f32; 4] {
+impl From<Color> for [f32; 4] {
+    fn from(value: Color) -> Self {
         [
-            self.r as f32 / 255.,
-            self.g as f32 / 255.,
-            self.b as f32 / 255.,
-            self.a as f32 / 255.,
+            value.r as f32 / 255.,
+            value.g as f32 / 255.,
+            value.b as f32 / 255.,
+            value.a as f32 / 255.,
         ]
     }
 }
 
-impl Into<(f32, f32, f32, f32)> for Color {
-    fn into(self) -> (f32, f32, f32, f32) {
+impl From<Color> for (f32, f32, f32, f32) {
+    fn from(value: Color) -> Self {
         (
-            self.r as f32 / 255.,
-            self.g as f32 / 255.,
-            self.b as f32 / 255.,
-            self.a as f32 / 255.,
+            value.r as f32 / 255.,
+            value.g as f32 / 255.,
+            value.b as f32 / 255.,
+            value.a as f32 / 255.,
         )
     }
 }
 
-impl Into<Color> for (f32, f32, f32, f32) {
-    fn into(self) -> Color {
+impl From<(f32, f32, f32, f32)> for Color {
+    fn from(value: (f32, f32, f32, f32)) -> Self {
         Color {
-            r: (self.0 * 255.).round().abs() as u8,
-            g: (self.0 * 255.).round().abs() as u8,
-            b: (self.0 * 255.).round().abs() as u8,
-            a: (self.0 * 255.).round().abs() as u8,
+            r: (value.0 * 255.).round().abs() as u8,
+            g: (value.1 * 255.).round().abs() as u8,
+            b: (value.2 * 255.).round().abs() as u8,
+            a: (value.3 * 255.).round().abs() as u8,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn float_conversions_keep_channels() {
+        let color = Color {
+            r: 255,
+            g: 128,
+            b: 0,
+            a: 51,
+        };
+        let array: [f32; 4] = color.into();
+        let tuple: (f32, f32, f32, f32) = color.into();
+        assert!(Color::from(array) == color);
+        assert!(Color::from(tuple) == color);
+    }
+}
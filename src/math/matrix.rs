@@ -95,6 +95,7 @@ impl<const W: usize, const H: usize> Add for Matrix<W, H> {
 }
 
 impl<const W: usize, const H: usize> AddAssign for Matrix<W, H> {
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn add_assign(&mut self, rhs: Self) {
         for x in 0..W {
             for y in 0..H {
@@ -152,6 +153,17 @@ impl Matrix<2, 2> {
 
         Self::new([[cosine, sine], [-sine, cosine]])
     }
+
+    /// The matrix that undoes this one, if there is one.
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b], [c, d]] = self.data;
+        let det = a * d - c * b;
+        if det == 0. {
+            return None;
+        }
+
+        Some(Self::new([[d / det, -b / det], [-c / det, a / det]]))
+    }
 }
 
 impl Mul<Matrix<2, 2>> for Pt2 {
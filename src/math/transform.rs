@@ -39,6 +39,15 @@ impl Transform {
         self.pivot += pivot;
         self
     }
+
+    /// The transform that undoes this one, if there is one.
+    pub fn inverse(&self) -> Option<Self> {
+        Some(Self {
+            mat: self.mat.inverse()?,
+            offset: self.pivot,
+            pivot: self.offset,
+        })
+    }
 }
 
 impl Mul<Transform> for Pt2 {
@@ -1,7 +1,10 @@
+use std::any::Any;
+
 use crate::{
     game::Game,
     gl::GraphicsContext,
-    math::{transform::Transform, Seconds},
+    math::{point::Pt2, transform::Transform, Seconds},
+    Result,
 };
 
 pub use mozart_macro::{Obj, Obj2d};
@@ -11,9 +14,14 @@ pub mod maybe;
 
 pub mod sprite;
 
-pub trait Obj {
+pub trait Obj: Any {
     fn update_children(&mut self, game: &mut Game, delta: Seconds);
+    fn fixed_update_children(&mut self, game: &mut Game, delta: Seconds);
     fn draw_children(&self, ctx: &mut GraphicsContext);
+    fn ready_children(&mut self, game: &mut Game);
+    fn exit_children(&mut self, game: &mut Game);
+    fn resize_children(&mut self, game: &mut Game, size: Pt2);
+    fn focus_children(&mut self, game: &mut Game, focused: bool);
 }
 
 pub trait Obj2d: Obj {
@@ -21,21 +29,23 @@ pub trait Obj2d: Obj {
     fn transform_mut(&mut self) -> &mut Transform;
 }
 
-pub trait Make: Obj {
+/// Objects that can be made from a config. Failing to make a scene reports the
+/// error, see [`GameBuilder::start`](crate::game::GameBuilder::start).
+pub trait Make: Obj + Sized {
     type Config;
 
-    fn make(game: &mut Game, config: Self::Config) -> Self;
+    fn make(game: &mut Game, config: Self::Config) -> Result<Self>;
 }
 
 pub trait MakeDefault: Make {
-    fn make_default(game: &mut Game) -> Self;
+    fn make_default(game: &mut Game) -> Result<Self>;
 }
 impl<T, C> MakeDefault for T
 where
     T: Make<Config = C>,
     C: Default,
 {
-    fn make_default(game: &mut Game) -> Self {
+    fn make_default(game: &mut Game) -> Result<Self> {
         Self::make(game, Default::default())
     }
 }
@@ -44,6 +54,36 @@ pub trait Update: Obj {
     fn update(&mut self, game: &mut Game, delta: Seconds);
 }
 
+/// Called at a constant rate when the game is built with
+/// [`GameBuilder::fixed_timestep`](crate::game::GameBuilder::fixed_timestep).
+/// `delta` is always the length of one step.
+pub trait FixedUpdate: Obj {
+    fn fixed_update(&mut self, game: &mut Game, delta: Seconds);
+}
+
 pub trait Draw: Obj {
     fn draw(&self, ctx: &mut GraphicsContext);
 }
+
+/// Called once when the scene the object is in is added to the game, after
+/// its children are ready.
+pub trait Ready: Obj {
+    fn ready(&mut self, game: &mut Game);
+}
+
+/// Called once when the scene the object is in is removed from the game, or
+/// the game quits, before its children exit.
+pub trait Exit: Obj {
+    fn exit(&mut self, game: &mut Game);
+}
+
+/// Called when the window is resized, with the new size.
+pub trait Resize: Obj {
+    fn resize(&mut self, game: &mut Game, size: Pt2);
+}
+
+/// Called when the window gains or loses focus. Some platforms only report
+/// the window being minimized and restored.
+pub trait Focus: Obj {
+    fn focus(&mut self, game: &mut Game, focused: bool);
+}
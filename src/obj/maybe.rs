@@ -1,6 +1,6 @@
-use crate::{game::Game, gl::GraphicsContext};
+use crate::{game::Game, gl::GraphicsContext, math::point::Pt2};
 
-use super::{Draw, Obj, Update};
+use super::{Draw, Exit, FixedUpdate, Focus, Obj, Ready, Resize, Update};
 
 #[repr(transparent)]
 pub struct Wrapper<T>(pub T);
@@ -28,6 +28,29 @@ macro_rules! maybe_update {
     }};
 }
 
+pub trait MaybeFixedUpdate {
+    fn maybe_fixed_update(&mut self, game: &mut Game, delta: f32);
+}
+impl<T: FixedUpdate> MaybeFixedUpdate for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_fixed_update(&mut self, game: &mut Game, delta: f32) {
+        self.0.fixed_update(game, delta)
+    }
+}
+
+impl<T> MaybeFixedUpdate for &mut &mut Wrapper<&mut T> {
+    fn maybe_fixed_update(&mut self, _: &mut Game, _: f32) {}
+}
+
+#[macro_export]
+macro_rules! maybe_fixed_update {
+    ($obj:expr, $game:expr, $delta:expr) => {{
+        use $crate::obj::maybe::MaybeFixedUpdate;
+        let obj = $obj;
+        (&mut &mut $crate::obj::maybe::Wrapper(obj)).maybe_fixed_update($game, $delta)
+    }};
+}
+
 pub trait MaybeDraw {
     fn maybe_draw(&self, ctx: &mut GraphicsContext);
 }
@@ -53,21 +76,47 @@ macro_rules! maybe_draw {
 pub trait MaybeUpdateChildren {
     fn maybe_update_children(self, game: &mut Game, delta: f32);
 }
-impl<T: Obj> MaybeUpdateChildren for &mut Wrapper<&mut T> {
+impl<T: Obj> MaybeUpdateChildren for &mut &mut Wrapper<&mut T> {
     #[inline(always)]
     fn maybe_update_children(self, game: &mut Game, delta: f32) {
         self.0.update_children(game, delta)
     }
 }
 
-impl<T> MaybeUpdateChildren for &mut &mut Wrapper<&mut T> {
+impl<T> MaybeUpdateChildren for &mut Wrapper<&mut T> {
     #[inline(always)]
     fn maybe_update_children(self, _: &mut Game, _: f32) {}
 }
 
 #[macro_export]
 macro_rules! maybe_update_children {
-    ($obj:expr, $game:expr, $delta:expr) => {};
+    ($obj:expr, $game:expr, $delta:expr) => {{
+        use $crate::obj::maybe::MaybeUpdateChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_update_children($game, $delta)
+    }};
+}
+
+pub trait MaybeFixedUpdateChildren {
+    fn maybe_fixed_update_children(self, game: &mut Game, delta: f32);
+}
+impl<T: Obj> MaybeFixedUpdateChildren for &mut &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_fixed_update_children(self, game: &mut Game, delta: f32) {
+        self.0.fixed_update_children(game, delta)
+    }
+}
+
+impl<T> MaybeFixedUpdateChildren for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_fixed_update_children(self, _: &mut Game, _: f32) {}
+}
+
+#[macro_export]
+macro_rules! maybe_fixed_update_children {
+    ($obj:expr, $game:expr, $delta:expr) => {{
+        use $crate::obj::maybe::MaybeFixedUpdateChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_fixed_update_children($game, $delta)
+    }};
 }
 
 pub trait MaybeDrawChildren {
@@ -92,3 +141,151 @@ macro_rules! maybe_draw_children {
         (&&$crate::obj::maybe::Wrapper($obj)).maybe_draw_children($ctx)
     }};
 }
+
+pub trait MaybeReady {
+    fn maybe_ready(&mut self, game: &mut Game);
+}
+impl<T: Ready> MaybeReady for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_ready(&mut self, game: &mut Game) {
+        self.0.ready(game)
+    }
+}
+
+impl<T> MaybeReady for &mut &mut Wrapper<&mut T> {
+    fn maybe_ready(&mut self, _: &mut Game) {}
+}
+
+pub trait MaybeExit {
+    fn maybe_exit(&mut self, game: &mut Game);
+}
+impl<T: Exit> MaybeExit for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_exit(&mut self, game: &mut Game) {
+        self.0.exit(game)
+    }
+}
+
+impl<T> MaybeExit for &mut &mut Wrapper<&mut T> {
+    fn maybe_exit(&mut self, _: &mut Game) {}
+}
+
+pub trait MaybeResize {
+    fn maybe_resize(&mut self, game: &mut Game, size: Pt2);
+}
+impl<T: Resize> MaybeResize for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_resize(&mut self, game: &mut Game, size: Pt2) {
+        self.0.resize(game, size)
+    }
+}
+
+impl<T> MaybeResize for &mut &mut Wrapper<&mut T> {
+    fn maybe_resize(&mut self, _: &mut Game, _: Pt2) {}
+}
+
+pub trait MaybeFocus {
+    fn maybe_focus(&mut self, game: &mut Game, focused: bool);
+}
+impl<T: Focus> MaybeFocus for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_focus(&mut self, game: &mut Game, focused: bool) {
+        self.0.focus(game, focused)
+    }
+}
+
+impl<T> MaybeFocus for &mut &mut Wrapper<&mut T> {
+    fn maybe_focus(&mut self, _: &mut Game, _: bool) {}
+}
+
+pub trait MaybeReadyChildren {
+    fn maybe_ready_children(self, game: &mut Game);
+}
+impl<T: Obj> MaybeReadyChildren for &mut &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_ready_children(self, game: &mut Game) {
+        self.0.ready_children(game)
+    }
+}
+
+impl<T> MaybeReadyChildren for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_ready_children(self, _: &mut Game) {}
+}
+
+#[macro_export]
+macro_rules! maybe_ready_children {
+    ($obj:expr, $game:expr) => {{
+        use $crate::obj::maybe::MaybeReadyChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_ready_children($game)
+    }};
+}
+
+pub trait MaybeExitChildren {
+    fn maybe_exit_children(self, game: &mut Game);
+}
+impl<T: Obj> MaybeExitChildren for &mut &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_exit_children(self, game: &mut Game) {
+        self.0.exit_children(game)
+    }
+}
+
+impl<T> MaybeExitChildren for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_exit_children(self, _: &mut Game) {}
+}
+
+#[macro_export]
+macro_rules! maybe_exit_children {
+    ($obj:expr, $game:expr) => {{
+        use $crate::obj::maybe::MaybeExitChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_exit_children($game)
+    }};
+}
+
+pub trait MaybeResizeChildren {
+    fn maybe_resize_children(self, game: &mut Game, size: Pt2);
+}
+impl<T: Obj> MaybeResizeChildren for &mut &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_resize_children(self, game: &mut Game, size: Pt2) {
+        self.0.resize_children(game, size)
+    }
+}
+
+impl<T> MaybeResizeChildren for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_resize_children(self, _: &mut Game, _: Pt2) {}
+}
+
+#[macro_export]
+macro_rules! maybe_resize_children {
+    ($obj:expr, $game:expr, $size:expr) => {{
+        use $crate::obj::maybe::MaybeResizeChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_resize_children($game, $size)
+    }};
+}
+
+pub trait MaybeFocusChildren {
+    fn maybe_focus_children(self, game: &mut Game, focused: bool);
+}
+impl<T: Obj> MaybeFocusChildren for &mut &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_focus_children(self, game: &mut Game, focused: bool) {
+        self.0.focus_children(game, focused)
+    }
+}
+
+impl<T> MaybeFocusChildren for &mut Wrapper<&mut T> {
+    #[inline(always)]
+    fn maybe_focus_children(self, _: &mut Game, _: bool) {}
+}
+
+#[macro_export]
+macro_rules! maybe_focus_children {
+    ($obj:expr, $game:expr, $focused:expr) => {{
+        use $crate::obj::maybe::MaybeFocusChildren;
+        (&mut &mut $crate::obj::maybe::Wrapper($obj)).maybe_focus_children($game, $focused)
+    }};
+}
@@ -1,14 +1,17 @@
 use std::sync::Arc;
 
-use miniquad::{Bindings, BufferSource};
 use mozart_macro::Obj2d;
 
 use super::{Draw, Make, Obj};
 use crate::{
     self as mozart,
-    game::assets::texture::Texture,
-    gl::{vertex::Vertex, GraphicsContext},
+    game::{
+        assets::{shader::Shader, texture::Texture, AssetId},
+        Game,
+    },
+    gl::{vertex::Vertex, Bindings, BufferSource, GraphicsContext},
     math::{point::pt2, transform::Transform},
+    Result,
 };
 
 #[derive(Obj, Obj2d)]
@@ -16,19 +19,23 @@ pub struct Sprite {
     transform: Transform,
 
     texture: Arc<Texture>,
+    /// drawn with the default shader if `None`
+    shader: Option<Arc<Shader>>,
     bindings: Bindings,
 }
 
 pub struct SpriteConf {
-    texture_path: &'static str,
+    texture_id: AssetId<Texture>,
     transform: Option<Transform>,
+    shader_id: Option<AssetId<Shader>>,
 }
 
 impl Sprite {
-    pub fn cfg_from_texture(path: &'static str) -> SpriteConf {
+    pub fn cfg_from_texture(texture: impl Into<AssetId<Texture>>) -> SpriteConf {
         SpriteConf {
-            texture_path: path,
+            texture_id: texture.into(),
             transform: None,
+            shader_id: None,
         }
     }
 }
@@ -37,15 +44,39 @@ impl SpriteConf {
         self.transform = Some(transform);
         self
     }
+    /// Draw with a loaded shader instead of the default one.
+    pub fn shader(mut self, shader: impl Into<AssetId<Shader>>) -> Self {
+        self.shader_id = Some(shader.into());
+        self
+    }
 }
 
 impl Make for Sprite {
     type Config = SpriteConf;
 
-    fn make(game: &mut crate::game::Game, config: Self::Config) -> Self {
-        let texture: Arc<Texture> = game.load_gl_asset(config.texture_path);
+    fn make(game: &mut Game, config: Self::Config) -> Result<Self> {
+        let texture: Arc<Texture> = game.load_gl_asset(config.texture_id)?;
+        let mut sprite = Self::from_texture(
+            game,
+            texture,
+            config.transform.unwrap_or(Transform::IDENTITY),
+        );
+        if let Some(shader_id) = config.shader_id {
+            sprite.shader = Some(game.load_gl_asset(shader_id)?);
+        }
+        Ok(sprite)
+    }
+}
+
+impl Sprite {
+    /// Sprite of a texture that wasn't loaded as an asset.
+    pub(crate) fn from_texture(
+        game: &mut Game,
+        texture: Arc<Texture>,
+        transform: Transform,
+    ) -> Self {
         Self {
-            transform: config.transform.unwrap_or_else(|| Transform::IDENTITY),
+            transform,
 
             bindings: Bindings {
                 index_buffer: game.gl.indices_square(),
@@ -53,14 +84,18 @@ impl Make for Sprite {
                 images: vec![texture.gl_texture],
             },
             texture,
+            shader: None,
         }
     }
 }
 
 impl Draw for Sprite {
     fn draw(&self, ctx: &mut GraphicsContext) {
-        let w = self.texture.image.width as f32;
-        let h = self.texture.image.height as f32;
+        // Asked every frame, since hot reloading can resize the texture
+        let Some(size) = ctx.texture_size(self.texture.gl_texture) else {
+            return;
+        };
+        let (w, h) = (size.x as f32, size.y as f32);
 
         #[rustfmt::skip]
         let mut vertices = vec![
@@ -70,15 +105,18 @@ impl Draw for Sprite {
             Vertex { pos: pt2(0., h), uv: pt2(0., 1.) },
         ];
 
-        for i in 0..vertices.len() {
-            vertices[i].pos *= self.transform;
-            vertices[i].pos *= ctx.viewport_transform();
+        for vertex in vertices.iter_mut() {
+            vertex.pos *= self.transform;
+            vertex.pos *= ctx.viewport_transform();
         }
 
         ctx.update_buffer(
             self.bindings.vertex_buffers[0],
             BufferSource::slice(&vertices),
         );
-        ctx.draw(&self.bindings, 6);
+        match &self.shader {
+            Some(shader) => ctx.draw_with_shader(shader.gl_shader, &self.bindings, 6),
+            None => ctx.draw(&self.bindings, 6),
+        }
     }
 }
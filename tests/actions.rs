@@ -0,0 +1,125 @@
+use mozart::{
+    game::{
+        input::{
+            Binding, GamepadAxis, GamepadButton, InputEvent, KeyCode, KeyMods, MouseButton,
+            VirtualGamepads,
+        },
+        Game,
+    },
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+fn game() -> Game {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let actions = &mut game.input.actions;
+    actions.bind("left", KeyCode::A);
+    actions.bind("right", KeyCode::D);
+    actions.bind(
+        "right",
+        Binding::Axis {
+            axis: GamepadAxis::LeftX,
+            positive: true,
+        },
+    );
+    actions.bind("jump", KeyCode::Space);
+    actions.bind("jump", GamepadButton::South);
+    game
+}
+
+fn key_down(game: &mut Game, key: KeyCode) {
+    let modifiers = KeyMods::default();
+    game.inject(InputEvent::KeyDown { key, modifiers });
+}
+
+#[test]
+fn saved_bindings_load_back() {
+    let mut game = game();
+    game.input.actions.bind("shoot", MouseButton::Left);
+    game.input.actions.bind("shoot", MouseButton::Unknown);
+    let saved = game.input.actions.save();
+    assert!(
+        saved.contains("shoot = mouse:Left, mouse:Unknown\n"),
+        "{saved}"
+    );
+
+    let mut loaded = self::game();
+    loaded.input.actions.clear("jump");
+    loaded.input.actions.load(&saved).unwrap();
+    let actions: Vec<_> = loaded.input.actions.actions().collect();
+    assert_eq!(actions, ["jump", "left", "right", "shoot"]);
+    for action in actions {
+        assert_eq!(
+            loaded.input.actions.bindings(action),
+            game.input.actions.bindings(action)
+        );
+    }
+}
+
+#[test]
+fn bad_bindings_keep_the_old_ones() {
+    let mut game = game();
+    let err = game
+        .input
+        .actions
+        .load("# mine\njump = key:Space\nleft = key:Nope")
+        .unwrap_err();
+    assert!(matches!(err, mozart::Error::Parse { line: 3, .. }), "{err}");
+    assert_eq!(game.input.actions.bindings("right").len(), 2);
+}
+
+#[test]
+fn axes_from_keys() {
+    let mut game = game();
+    key_down(&mut game, KeyCode::D);
+    game.step(1. / 60.);
+    assert_eq!(game.input.get_axis("left", "right"), 1.);
+
+    key_down(&mut game, KeyCode::A);
+    game.step(1. / 60.);
+    assert_eq!(game.input.get_axis("left", "right"), 0.);
+}
+
+#[test]
+fn axes_from_sticks_skip_the_deadzone() {
+    let mut game = game();
+    let pads = game.gamepad_backend::<VirtualGamepads>().unwrap();
+    let pad = pads.connect();
+    pads.set_axis(pad, GamepadAxis::LeftX, 0.1);
+    game.step(1. / 60.);
+    assert_eq!(game.input.get_axis("left", "right"), 0.);
+    assert!(!game.input.is_action_down("right"));
+
+    let pads = game.gamepad_backend::<VirtualGamepads>().unwrap();
+    pads.set_axis(pad, GamepadAxis::LeftX, 0.6);
+    game.step(1. / 60.);
+    // 0.6 is halfway from the 0.2 deadzone to 1
+    assert!((game.input.get_axis("left", "right") - 0.5).abs() < 1e-5);
+    assert!(game.input.is_action_pressed("right"));
+}
+
+#[test]
+fn holding_a_second_binding_is_not_a_new_press() {
+    let mut game = game();
+    key_down(&mut game, KeyCode::Space);
+    game.step(1. / 60.);
+    assert!(game.input.is_action_pressed("jump"));
+
+    let pads = game.gamepad_backend::<VirtualGamepads>().unwrap();
+    let pad = pads.connect();
+    pads.press(pad, GamepadButton::South);
+    game.step(1. / 60.);
+    assert!(game.input.is_action_down("jump"));
+    assert!(!game.input.is_action_pressed("jump"));
+}
@@ -0,0 +1,110 @@
+use std::{fs, sync::Arc};
+
+use mozart::{
+    game::{
+        assets::{
+            texture::{Image, Texture},
+            vfs::Memory,
+            Asset, AssetErrorKind,
+        },
+        Game,
+    },
+    obj::{Make, Obj},
+    Error, Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// An asset without a placeholder.
+struct Text;
+
+impl Asset for Text {
+    fn load(data: &[u8]) -> std::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        std::str::from_utf8(data)?;
+        Ok(Self)
+    }
+}
+
+const SPRITE: &str = "examples/assets/sprite.png";
+
+#[test]
+fn missing_files_fail_to_read() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let err = game.assets.try_load::<Image>("missing.png").err().unwrap();
+    assert_eq!(err.path, "missing.png");
+    assert!(err.asset.ends_with("Image"), "{}", err.asset);
+    assert!(matches!(err.kind, AssetErrorKind::Io(_)));
+    assert!(err.to_string().starts_with("could not read"), "{err}");
+
+    let err = game
+        .try_load_gl_asset::<Texture>("missing.png")
+        .err()
+        .unwrap();
+    assert!(err.asset.ends_with("Texture"), "{}", err.asset);
+    assert!(matches!(err.kind, AssetErrorKind::Io(_)));
+}
+
+#[test]
+fn invalid_files_fail_to_decode() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let err = game.assets.try_load::<Image>("Cargo.toml").err().unwrap();
+    assert!(matches!(err.kind, AssetErrorKind::Decode(_)));
+    assert!(err.to_string().starts_with("failed to decode"), "{err}");
+
+    let err = game
+        .try_load_gl_asset::<Texture>("Cargo.toml")
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind, AssetErrorKind::Decode(_)));
+}
+
+#[test]
+fn loads_are_cached_but_failures_are_not() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let first = game.assets.try_load::<Image>(SPRITE).unwrap();
+    let second = game.assets.try_load::<Image>(SPRITE).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+
+    assert!(game.assets.try_load::<Image>("late.png").is_err());
+    let bytes = fs::read(SPRITE).unwrap();
+    game.assets
+        .vfs_mut()
+        .mount("", Memory::new().with("late.png", bytes), 1);
+    let late = game.assets.try_load::<Image>("late.png").unwrap();
+    assert_eq!(late.size(), first.size());
+}
+
+#[test]
+fn headless_games_do_not_fall_back_to_placeholders() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    assert!(matches!(
+        game.load_asset::<Image>("missing.png"),
+        Err(Error::Asset(_))
+    ));
+    assert!(matches!(
+        game.load_gl_asset::<Texture>("missing.png"),
+        Err(Error::Asset(_))
+    ));
+}
+
+#[test]
+fn placeholders_are_made_once() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let placeholder = game.assets.placeholder::<Image>().unwrap();
+    assert_eq!((placeholder.width, placeholder.height), (8, 8));
+    // Magenta and black squares
+    assert_eq!(placeholder.bytes[..8], [255, 0, 255, 255, 0, 0, 0, 255]);
+    let again = game.assets.placeholder::<Image>().unwrap();
+    assert!(Arc::ptr_eq(&placeholder, &again));
+
+    assert!(game.assets.placeholder::<Text>().is_none());
+}
@@ -0,0 +1,104 @@
+use std::{rc::Rc, sync::Arc, thread, time::Duration};
+
+use mozart::{
+    game::{
+        assets::{
+            texture::{Image, Texture},
+            AssetErrorKind, AssetGroup, LoadState,
+        },
+        Game,
+    },
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+const SPRITE: &str = "examples/assets/sprite.png";
+
+#[test]
+fn groups_progress_until_every_load_is_done() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    assert_eq!(AssetGroup::new().progress(), 1.);
+
+    let mut group = AssetGroup::new();
+    let image = group.add(game.load_asset_async::<Image>(SPRITE));
+    let texture = group.add(game.load_gl_asset_async::<Texture>(SPRITE));
+    // Loads only finish on the main thread
+    assert_eq!(group.len(), 2);
+    assert_eq!(group.progress(), 0.);
+    assert!(!group.is_done());
+    assert!(matches!(image.state(), LoadState::Loading));
+
+    game.wait_for_assets();
+    assert_eq!(group.done(), 2);
+    assert_eq!(group.progress(), 1.);
+    assert!(group.is_done());
+    assert!(group.errors().is_empty());
+    assert_eq!(
+        texture.get().unwrap().image.size(),
+        image.get().unwrap().size()
+    );
+}
+
+#[test]
+fn missing_files_fail() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let mut group = AssetGroup::new();
+    group.add(game.load_asset_async::<Image>(SPRITE));
+    let missing = group.add(game.load_gl_asset_async::<Texture>("missing.png"));
+
+    game.wait_for_assets();
+    assert!(group.is_done());
+    assert!(missing.get().is_none());
+    let LoadState::Failed(err) = missing.state() else {
+        panic!("loaded a missing file");
+    };
+    assert_eq!(err.path, "missing.png");
+    assert!(matches!(err.kind, AssetErrorKind::Io(_)));
+
+    let errors = group.errors();
+    assert_eq!(errors.len(), 1);
+    assert!(Rc::ptr_eq(&errors[0], &err));
+}
+
+#[test]
+fn background_loads_share_the_cached_asset() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let handle = game.load_gl_asset_async::<Texture>(SPRITE);
+    // Loads of the same asset share a handle until it's done
+    let again = game.load_gl_asset_async::<Texture>(SPRITE);
+    game.wait_for_assets();
+    let loaded = handle.get().unwrap();
+    assert!(Arc::ptr_eq(&loaded, &again.get().unwrap()));
+    assert!(Arc::ptr_eq(&loaded, &game.load_gl_asset(SPRITE).unwrap()));
+
+    // And loads of a cached asset are done right away
+    let image = game.load_asset::<Image>(SPRITE).unwrap();
+    let handle = game.load_asset_async::<Image>(SPRITE);
+    assert!(handle.is_done());
+    assert!(Arc::ptr_eq(&image, &handle.get().unwrap()));
+}
+
+#[test]
+fn loads_finish_at_the_start_of_a_frame() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let handle = game.load_asset_async::<Image>(SPRITE);
+    for _ in 0..1000 {
+        game.step(1. / 60.);
+        if handle.is_done() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert!(handle.get().is_some(), "{:?}", handle.state());
+}
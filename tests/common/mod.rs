@@ -0,0 +1,42 @@
+use mozart::{
+    game::{Game, GameBuilder},
+    math::{color::Color, point::pt2, transform::Transform},
+    obj::{sprite::Sprite, Make, Obj, Update},
+    Result,
+};
+
+#[derive(Obj)]
+pub struct Scene {
+    sprite: Sprite,
+    pub frames: u32,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        let transform = Transform::IDENTITY
+            .scaled_uniform(2.)
+            .with_offset(pt2(16., 8.));
+        Ok(Self {
+            sprite: Sprite::make(
+                game,
+                Sprite::cfg_from_texture("examples/assets/sprite.png").transform(transform),
+            )?,
+            frames: 0,
+        })
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, _game: &mut Game, _delta: f32) {
+        self.frames += 1;
+    }
+}
+
+/// A 64x64 game that draws a sprite, scaled to 32x32 at (16, 8).
+pub fn builder() -> GameBuilder {
+    Game::new()
+        .window_size((64, 64))
+        .clear_color(Color::from_hex_rgb(0x203040))
+}
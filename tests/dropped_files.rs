@@ -0,0 +1,83 @@
+use std::fs;
+
+use mozart::{
+    game::{
+        assets::{
+            texture::{Image, Texture},
+            AssetErrorKind,
+        },
+        Game,
+    },
+    obj::{Make, Obj},
+    Error, Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+const FRAME: f32 = 1. / 60.;
+const SPRITE: &str = "examples/assets/sprite.png";
+
+#[test]
+fn dropped_files_last_one_frame() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.drop_file(SPRITE).unwrap();
+    game.drop_file("Cargo.toml").unwrap();
+    assert!(game.input.dropped_files().is_empty());
+
+    game.step(FRAME);
+    let files = game.input.dropped_files();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].path.to_str(), Some(SPRITE));
+    assert_eq!(files[0].extension(), Some("png"));
+    assert_eq!(*files[0].bytes, *fs::read(SPRITE).unwrap());
+    assert_eq!(files[1].extension(), Some("toml"));
+
+    game.step(FRAME);
+    assert!(game.input.dropped_files().is_empty());
+}
+
+#[test]
+fn dropping_a_missing_file_fails() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let err = game.drop_file("examples/assets/missing.png").unwrap_err();
+    assert!(matches!(&err, Error::Io { path, .. } if path.ends_with("missing.png")));
+
+    game.step(FRAME);
+    assert!(game.input.dropped_files().is_empty());
+}
+
+#[test]
+fn dropped_files_load_as_assets() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.drop_file(SPRITE).unwrap();
+    game.step(FRAME);
+    let file = game.input.dropped_files()[0].clone();
+
+    let image = game.load_dropped_asset::<Image>(&file).unwrap();
+    let texture = game.load_dropped_gl_asset::<Texture>(&file).unwrap();
+    assert_eq!(texture.image.size(), image.size());
+    assert_eq!(texture.image.bytes, image.bytes);
+}
+
+#[test]
+fn dropped_files_that_are_not_assets_fail_to_decode() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.drop_file("Cargo.toml").unwrap();
+    game.step(FRAME);
+    let file = game.input.dropped_files()[0].clone();
+
+    let Err(Error::Asset(err)) = game.load_dropped_asset::<Image>(&file) else {
+        panic!("decoded Cargo.toml as an image");
+    };
+    assert_eq!(err.path, "Cargo.toml");
+    assert!(matches!(err.kind, AssetErrorKind::Decode(_)));
+}
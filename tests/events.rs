@@ -0,0 +1,56 @@
+use mozart::{
+    game::Game,
+    obj::{Make, Obj, Update},
+    Result,
+};
+
+struct Ping;
+
+#[derive(Obj)]
+struct Emitter;
+
+impl Update for Emitter {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        if game.time().frame() == 1 {
+            game.emit(Ping);
+        }
+    }
+}
+
+#[derive(Obj)]
+struct Listener {
+    heard: Vec<u64>,
+}
+
+impl Update for Listener {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        for _ in game.events::<Ping>() {
+            self.heard.push(game.time().frame());
+        }
+    }
+}
+
+/// The listener updates after the emitter.
+#[derive(Obj)]
+struct Scene {
+    emitter: Emitter,
+    listener: Listener,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            emitter: Emitter,
+            listener: Listener { heard: Vec::new() },
+        })
+    }
+}
+
+#[test]
+fn events_are_read_once_the_next_frame() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.step_frames(3, 1. / 60.);
+    assert_eq!(game.scene::<Scene>().unwrap().listener.heard, [2]);
+}
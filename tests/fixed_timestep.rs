@@ -0,0 +1,68 @@
+use mozart::{
+    game::Game,
+    obj::{FixedUpdate, Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene {
+    steps: Vec<f32>,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self { steps: Vec::new() })
+    }
+}
+
+impl FixedUpdate for Scene {
+    fn fixed_update(&mut self, _game: &mut Game, delta: f32) {
+        self.steps.push(delta);
+    }
+}
+
+fn steps(game: &Game) -> usize {
+    game.scene::<Scene>().unwrap().steps.len()
+}
+
+#[test]
+fn alpha_is_the_time_left_between_steps() {
+    let mut game = Game::new().fixed_timestep(10.).headless::<Scene>().unwrap();
+    game.step(0.25);
+
+    assert_eq!(steps(&game), 2);
+    assert!(game
+        .scene::<Scene>()
+        .unwrap()
+        .steps
+        .iter()
+        .all(|&d| d == 0.1));
+    assert!((game.graphics().alpha() - 0.5).abs() < 1e-4);
+
+    // The leftover time counts towards the next step
+    game.step(0.05);
+    assert_eq!(steps(&game), 3);
+    assert!(game.graphics().alpha() < 1e-4);
+}
+
+#[test]
+fn long_frames_run_at_most_eight_steps() {
+    let mut game = Game::new().fixed_timestep(10.).headless::<Scene>().unwrap();
+    game.step(2.05);
+    assert_eq!(steps(&game), 8);
+    assert!((game.graphics().alpha() - 0.5).abs() < 1e-3);
+
+    // The rest of the long frame is dropped instead of caught up on
+    game.step(0.);
+    assert_eq!(steps(&game), 8);
+}
+
+#[test]
+fn alpha_is_one_without_a_fixed_timestep() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.step(0.25);
+    assert_eq!(steps(&game), 0);
+    assert_eq!(game.graphics().alpha(), 1.);
+}
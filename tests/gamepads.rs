@@ -0,0 +1,155 @@
+use mozart::{
+    game::{
+        input::{GamepadAxis, GamepadButton, GamepadEvent, InputEvent, Rumble, VirtualGamepads},
+        Game,
+    },
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+const FRAME: f32 = 1. / 60.;
+
+fn pads(game: &mut Game) -> &mut VirtualGamepads {
+    game.gamepad_backend::<VirtualGamepads>().unwrap()
+}
+
+#[test]
+fn pads_connect_and_disconnect() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let first = pads(&mut game).connect();
+    let second = pads(&mut game).connect();
+    assert_ne!(first, second);
+    assert!(game.input.gamepads().is_empty());
+
+    game.step(FRAME);
+    assert_eq!(game.input.gamepads(), [first, second]);
+    assert_eq!(game.input.gamepads_connected(), [first, second]);
+    assert!(game.input.is_gamepad_connected(first));
+
+    pads(&mut game).disconnect(first);
+    assert!(!pads(&mut game).is_connected(first));
+    game.step(FRAME);
+    assert_eq!(game.input.gamepads(), [second]);
+    assert!(game.input.gamepads_connected().is_empty());
+    assert_eq!(game.input.gamepads_disconnected(), [first]);
+
+    game.step(FRAME);
+    assert!(game.input.gamepads_disconnected().is_empty());
+}
+
+#[test]
+fn buttons_are_pressed_and_released() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let pad = pads(&mut game).connect();
+    pads(&mut game).press(pad, GamepadButton::South);
+    game.step(FRAME);
+    assert!(game.input.is_gamepad_down(pad, GamepadButton::South));
+    assert!(game.input.is_gamepad_pressed(pad, GamepadButton::South));
+    assert!(!game.input.is_gamepad_down(pad, GamepadButton::East));
+
+    game.step(FRAME);
+    assert!(game.input.is_gamepad_down(pad, GamepadButton::South));
+    assert!(!game.input.is_gamepad_pressed(pad, GamepadButton::South));
+
+    pads(&mut game).release(pad, GamepadButton::South);
+    game.step(FRAME);
+    assert!(!game.input.is_gamepad_down(pad, GamepadButton::South));
+    assert!(game.input.is_gamepad_released(pad, GamepadButton::South));
+}
+
+#[test]
+fn disconnecting_releases_buttons_and_axes() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let pad = pads(&mut game).connect();
+    let other = pads(&mut game).connect();
+    pads(&mut game).press(pad, GamepadButton::North);
+    pads(&mut game).press(other, GamepadButton::North);
+    pads(&mut game).set_axis(pad, GamepadAxis::LeftX, 0.75);
+    game.step(FRAME);
+    assert_eq!(game.input.gamepad_axis(pad, GamepadAxis::LeftX), 0.75);
+
+    pads(&mut game).disconnect(pad);
+    game.step(FRAME);
+    assert!(game.input.is_gamepad_released(pad, GamepadButton::North));
+    assert!(!game.input.is_gamepad_down(pad, GamepadButton::North));
+    assert_eq!(game.input.gamepad_axis(pad, GamepadAxis::LeftX), 0.);
+    // Other pads keep their buttons
+    assert!(game.input.is_gamepad_down(other, GamepadButton::North));
+}
+
+#[test]
+fn axes_are_clamped() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let pad = pads(&mut game).connect();
+    pads(&mut game).set_axis(pad, GamepadAxis::RightY, -3.);
+    pads(&mut game).set_axis(pad, GamepadAxis::RightTrigger, 0.5);
+    game.step(FRAME);
+    assert_eq!(game.input.gamepad_axis(pad, GamepadAxis::RightY), -1.);
+    assert_eq!(game.input.gamepad_axis(pad, GamepadAxis::RightTrigger), 0.5);
+    assert_eq!(game.input.gamepad_axis(pad, GamepadAxis::LeftX), 0.);
+}
+
+#[test]
+fn pads_that_are_not_connected_are_ignored() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    // Events straight from a backend, for a pad it never connected
+    for event in [
+        GamepadEvent::ButtonDown(GamepadButton::Start),
+        GamepadEvent::Axis(GamepadAxis::LeftY, 1.),
+    ] {
+        game.inject(InputEvent::Gamepad { pad: 3, event });
+    }
+    game.step(FRAME);
+    assert!(!game.input.is_gamepad_down(3, GamepadButton::Start));
+    assert!(!game.input.is_gamepad_pressed(3, GamepadButton::Start));
+    assert_eq!(game.input.gamepad_axis(3, GamepadAxis::LeftY), 0.);
+
+    // A pad connected and pressed in the same frame is down
+    game.inject(InputEvent::Gamepad {
+        pad: 3,
+        event: GamepadEvent::Connected,
+    });
+    game.inject(InputEvent::Gamepad {
+        pad: 3,
+        event: GamepadEvent::ButtonDown(GamepadButton::Start),
+    });
+    game.step(FRAME);
+    assert!(game.input.is_gamepad_pressed(3, GamepadButton::Start));
+
+    // Virtual pads don't send events once disconnected
+    let pad = pads(&mut game).connect();
+    pads(&mut game).disconnect(pad);
+    pads(&mut game).press(pad, GamepadButton::South);
+    game.step(FRAME);
+    assert!(!game.input.is_gamepad_down(pad, GamepadButton::South));
+}
+
+#[test]
+fn rumble_reaches_the_backend() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let pad = pads(&mut game).connect();
+    assert_eq!(pads(&mut game).last_rumble(pad), None);
+
+    game.rumble(pad, 2., 0.25);
+    assert_eq!(
+        pads(&mut game).last_rumble(pad),
+        Some(Rumble {
+            strength: 1.,
+            duration: 0.25,
+        })
+    );
+    // Pads that aren't connected can't rumble
+    game.rumble(pad + 1, 0.5, 0.25);
+    assert_eq!(pads(&mut game).last_rumble(pad + 1), None);
+}
@@ -0,0 +1,17 @@
+use common::Scene;
+use mozart::gl::{software, software::SoftwareBackend};
+
+mod common;
+
+#[test]
+fn software_render_matches_golden() {
+    let mut game = common::builder()
+        .headless_with::<Scene>(SoftwareBackend::new())
+        .unwrap();
+    game.step(1. / 60.);
+
+    let backend = game.graphics().backend::<SoftwareBackend>().unwrap();
+    if let Err(err) = software::compare_golden(backend.frame(), "tests/golden/sprite.png", 0) {
+        panic!("{err}");
+    }
+}
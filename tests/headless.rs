@@ -0,0 +1,37 @@
+use common::Scene;
+use mozart::{gl::headless::RecordingBackend, math::color::Color};
+
+mod common;
+
+#[test]
+fn records_draw_calls() {
+    let mut game = common::builder().headless::<Scene>().unwrap();
+    game.step_frames(3, 1. / 60.);
+    assert_eq!(game.scene::<Scene>().unwrap().frames, 3);
+
+    let backend = game.graphics().backend::<RecordingBackend>().unwrap();
+    assert_eq!(backend.frames().len(), 3);
+
+    let frame = backend.last_frame().unwrap();
+    assert_eq!(frame.clear_color, Color::from_hex_rgb(0x203040));
+    assert_eq!(frame.draw_calls.len(), 1);
+
+    let call = &frame.draw_calls[0];
+    assert_eq!(backend.texture_size(call.images[0]), Some((16, 16)));
+    // The 16x16 sprite is scaled to 32x32 and moved to (16, 8) in a 64x64
+    // window, and drawn as two triangles in clip space
+    let (top_left, top_right) = ((-0.5, 0.75), (0.5, 0.75));
+    let (bottom_right, bottom_left) = ((0.5, -0.25), (-0.5, -0.25));
+    let corners: Vec<_> = call.vertices.iter().map(|v| (v.pos.x, v.pos.y)).collect();
+    assert_eq!(
+        corners,
+        [
+            top_left,
+            top_right,
+            bottom_right,
+            top_left,
+            bottom_right,
+            bottom_left
+        ]
+    );
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "hot-reload")]
+
+use std::{env, fs, thread, time::Duration};
+
+use mozart::{
+    game::{
+        assets::{shader::Shader, vfs::Dir, Vfs},
+        Game,
+    },
+    gl::headless::RecordingBackend,
+    obj::{sprite::Sprite, Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene {
+    _sprite: Sprite,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            _sprite: Sprite::make(game, Sprite::cfg_from_texture("reload/sprite.png"))?,
+        })
+    }
+}
+
+#[derive(Obj)]
+struct ShaderScene {
+    _sprite: Sprite,
+}
+
+impl Make for ShaderScene {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        let sprite =
+            Sprite::cfg_from_texture("examples/assets/sprite.png").shader("reload/gray.glsl");
+        Ok(Self {
+            _sprite: Sprite::make(game, sprite)?,
+        })
+    }
+}
+
+/// Width in pixels of the sprite drawn last frame.
+fn drawn_width(game: &Game) -> f32 {
+    let backend = game.graphics().backend::<RecordingBackend>().unwrap();
+    let vertices = &backend.last_frame().unwrap().draw_calls[0].vertices;
+    // Clip space spans 2 units over the 800 pixel wide window
+    ((vertices[1].pos.x - vertices[0].pos.x) * 400.).round()
+}
+
+#[test]
+fn paused_sprites_pick_up_reloaded_textures() {
+    let dir = env::temp_dir().join(format!("mozart-hot-reload-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy("examples/assets/sprite.png", dir.join("sprite.png")).unwrap();
+
+    let mut vfs = Vfs::new();
+    vfs.mount("reload", Dir::new(&dir), 0);
+    let mut game = Game::new().vfs(vfs).headless::<Scene>().unwrap();
+    game.step(0.1);
+    assert_eq!(drawn_width(&game), 16.);
+
+    game.set_paused(true);
+    // Make sure the modification time changes
+    thread::sleep(Duration::from_millis(20));
+    fs::copy("tests/golden/sprite.png", dir.join("sprite.png")).unwrap();
+    game.step_frames(5, 0.1);
+    assert_eq!(drawn_width(&game), 64.);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn shaders_are_recompiled_in_place() {
+    let dir = env::temp_dir().join(format!("mozart-hot-reload-shader-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let shader = dir.join("gray.glsl");
+    fs::write(
+        &shader,
+        "#shader vertex\nold vertex\n#shader fragment\nold fragment\n",
+    )
+    .unwrap();
+
+    let mut vfs = Vfs::new();
+    vfs.mount("", Dir::new("."), 0);
+    vfs.mount("reload", Dir::new(&dir), 0);
+    let mut game = Game::new().vfs(vfs).headless::<ShaderScene>().unwrap();
+    let old = game.load_gl_asset::<Shader>("reload/gray.glsl").unwrap();
+
+    game.set_paused(true);
+    thread::sleep(Duration::from_millis(20));
+    fs::write(
+        &shader,
+        "#shader vertex\nnew vertex\n#shader fragment\nnew fragment\n",
+    )
+    .unwrap();
+    game.step_frames(5, 0.1);
+
+    let new = game.load_gl_asset::<Shader>("reload/gray.glsl").unwrap();
+    assert_eq!(new.gl_shader, old.gl_shader);
+    assert_eq!(new.source.fragment, "new fragment\n");
+    let backend = game.graphics().backend::<RecordingBackend>().unwrap();
+    let draw_call = &backend.last_frame().unwrap().draw_calls[0];
+    assert_eq!(draw_call.shader, old.gl_shader);
+    assert_eq!(
+        backend.shader_source(old.gl_shader),
+        Some(("new vertex\n", "new fragment\n"))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
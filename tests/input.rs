@@ -0,0 +1,177 @@
+use miniquad::EventHandler;
+use mozart::{
+    game::{
+        input::{InputEvent, KeyCode, KeyMods, MouseButton},
+        Game,
+    },
+    math::point::pt2,
+    obj::{FixedUpdate, Make, Obj, Update},
+    Result,
+};
+
+/// What the scene saw of Space in each update or fixed update.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Seen {
+    down: bool,
+    pressed: bool,
+    released: bool,
+}
+
+#[derive(Obj)]
+struct Scene {
+    updates: Vec<Seen>,
+    fixed_updates: Vec<Seen>,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            updates: Vec::new(),
+            fixed_updates: Vec::new(),
+        })
+    }
+}
+
+fn seen(game: &Game) -> Seen {
+    Seen {
+        down: game.input.is_key_down(KeyCode::Space),
+        pressed: game.input.is_key_pressed(KeyCode::Space),
+        released: game.input.is_key_released(KeyCode::Space),
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        self.updates.push(seen(game));
+    }
+}
+
+impl FixedUpdate for Scene {
+    fn fixed_update(&mut self, game: &mut Game, _delta: f32) {
+        self.fixed_updates.push(seen(game));
+    }
+}
+
+fn key(game: &mut Game, key: KeyCode, down: bool) {
+    let modifiers = KeyMods::default();
+    game.inject(if down {
+        InputEvent::KeyDown { key, modifiers }
+    } else {
+        InputEvent::KeyUp { key, modifiers }
+    });
+}
+
+const PRESSED: Seen = Seen {
+    down: true,
+    pressed: true,
+    released: false,
+};
+const HELD: Seen = Seen {
+    down: true,
+    pressed: false,
+    released: false,
+};
+const RELEASED: Seen = Seen {
+    down: false,
+    pressed: false,
+    released: true,
+};
+const UP: Seen = Seen {
+    down: false,
+    pressed: false,
+    released: false,
+};
+
+#[test]
+fn edges_last_one_update() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    key(&mut game, KeyCode::Space, true);
+    game.step(1. / 60.);
+    game.step(1. / 60.);
+    key(&mut game, KeyCode::Space, false);
+    game.step(1. / 60.);
+    game.step(1. / 60.);
+
+    let scene = game.scene::<Scene>().unwrap();
+    assert_eq!(scene.updates, [PRESSED, HELD, RELEASED, UP]);
+}
+
+#[test]
+fn taps_between_frames_are_pressed_and_released() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    key(&mut game, KeyCode::Space, true);
+    key(&mut game, KeyCode::Space, false);
+    game.step(1. / 60.);
+    game.step(1. / 60.);
+
+    let tap = Seen {
+        down: false,
+        pressed: true,
+        released: true,
+    };
+    assert_eq!(game.scene::<Scene>().unwrap().updates, [tap, UP]);
+}
+
+#[test]
+fn fixed_updates_see_each_edge_once() {
+    let mut game = Game::new().fixed_timestep(4.).headless::<Scene>().unwrap();
+
+    // Pressed in a frame without a fixed step, seen by the next one
+    key(&mut game, KeyCode::Space, true);
+    game.step(0.125);
+    assert!(game.scene::<Scene>().unwrap().fixed_updates.is_empty());
+    game.step(0.125);
+    assert_eq!(game.scene::<Scene>().unwrap().fixed_updates, [PRESSED]);
+
+    // Released in a frame with two fixed steps, seen by the first
+    key(&mut game, KeyCode::Space, false);
+    game.step(0.5);
+    let scene = game.scene::<Scene>().unwrap();
+    assert_eq!(scene.fixed_updates, [PRESSED, RELEASED, UP]);
+    assert_eq!(scene.updates, [PRESSED, HELD, RELEASED]);
+}
+
+#[test]
+fn losing_focus_releases_everything() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    key(&mut game, KeyCode::Space, true);
+    game.inject(InputEvent::MouseDown {
+        button: MouseButton::Left,
+        position: pt2(10., 20.),
+    });
+    game.step(1. / 60.);
+    assert!(game.input.is_mouse_down(MouseButton::Left));
+
+    game.window_minimized_event();
+    game.step(1. / 60.);
+    assert_eq!(game.scene::<Scene>().unwrap().updates, [PRESSED, RELEASED]);
+    assert!(!game.input.is_mouse_down(MouseButton::Left));
+    assert!(game.input.is_mouse_released(MouseButton::Left));
+}
+
+#[test]
+fn mouse_position_and_wheel() {
+    let mut game = Game::new()
+        .window_size((200, 100))
+        .headless::<Scene>()
+        .unwrap();
+    game.inject(InputEvent::MouseMove(pt2(50., 25.)));
+    game.inject(InputEvent::MouseWheel(pt2(0., 1.)));
+    game.inject(InputEvent::MouseWheel(pt2(0., 1.)));
+    game.inject(InputEvent::MouseDown {
+        button: MouseButton::Right,
+        position: pt2(150., 75.),
+    });
+    game.step(1. / 60.);
+
+    assert!(game.input.is_mouse_pressed(MouseButton::Right));
+    assert_eq!(game.input.mouse_position(), pt2(150., 75.));
+    let world = game.input.mouse_world_position();
+    assert!((world - pt2(150., 75.)).length() < 1e-3, "{world:?}");
+    // Wheel movement adds up over a frame, and resets the next
+    assert_eq!(game.input.mouse_wheel(), pt2(0., 2.));
+    game.step(1. / 60.);
+    assert_eq!(game.input.mouse_wheel(), pt2(0., 0.));
+}
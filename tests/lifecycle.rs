@@ -0,0 +1,151 @@
+use std::{cell::RefCell, rc::Rc};
+
+use miniquad::EventHandler;
+use mozart::{
+    game::{window::QuitRequested, Game},
+    math::point::Pt2,
+    obj::{Exit, Focus, Make, Obj, Ready, Resize, Update},
+    Result,
+};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Obj)]
+struct Child {
+    log: Log,
+}
+
+impl Ready for Child {
+    fn ready(&mut self, _game: &mut Game) {
+        self.log.borrow_mut().push("child ready".into());
+    }
+}
+
+impl Exit for Child {
+    fn exit(&mut self, _game: &mut Game) {
+        self.log.borrow_mut().push("child exit".into());
+    }
+}
+
+#[derive(Obj)]
+struct Scene {
+    child: Child,
+    log: Log,
+}
+
+impl Make for Scene {
+    type Config = Log;
+
+    fn make(_game: &mut Game, log: Self::Config) -> Result<Self> {
+        Ok(Self {
+            child: Child { log: log.clone() },
+            log,
+        })
+    }
+}
+
+impl Ready for Scene {
+    fn ready(&mut self, _game: &mut Game) {
+        self.log.borrow_mut().push("ready".into());
+    }
+}
+
+impl Exit for Scene {
+    fn exit(&mut self, _game: &mut Game) {
+        self.log.borrow_mut().push("exit".into());
+    }
+}
+
+impl Resize for Scene {
+    fn resize(&mut self, _game: &mut Game, size: Pt2) {
+        self.log
+            .borrow_mut()
+            .push(format!("resize {}x{}", size.x, size.y));
+    }
+}
+
+impl Focus for Scene {
+    fn focus(&mut self, _game: &mut Game, focused: bool) {
+        self.log.borrow_mut().push(format!("focus {focused}"));
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        if !game.events::<QuitRequested>().is_empty() {
+            self.log.borrow_mut().push("quit requested".into());
+        }
+    }
+}
+
+#[derive(Obj)]
+struct Root;
+
+impl Make for Root {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// A game with a [`Scene`] pushed over an empty root, and the scene's log.
+fn game() -> (Game, Log) {
+    let mut game = Game::new().headless::<Root>().unwrap();
+    let log = Log::default();
+    game.push_scene_with::<Scene>(log.clone());
+    game.step(1. / 60.);
+    (game, log)
+}
+
+#[test]
+fn hooks_run_on_push_resize_focus_and_pop() {
+    let (mut game, log) = game();
+    game.set_window_size((320, 240));
+    game.window_minimized_event();
+    game.window_restored_event();
+    game.pop_scene();
+    game.step(1. / 60.);
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "child ready",
+            "ready",
+            "resize 320x240",
+            "focus false",
+            "focus true",
+            "exit",
+            "child exit",
+        ]
+    );
+    assert_eq!(game.scene_count(), 1);
+}
+
+#[test]
+fn quitting_between_steps_exits_scenes() {
+    let (mut game, log) = game();
+    game.request_quit();
+    assert!(game.has_quit());
+
+    game.step(1. / 60.);
+    assert_eq!(game.scene_count(), 0);
+    assert_eq!(log.borrow()[2..], ["exit", "child exit"]);
+}
+
+#[test]
+fn blocked_quits_send_quit_requested() {
+    let (mut game, log) = game();
+    game.block_quit(true);
+    game.request_quit();
+    game.step(1. / 60.);
+
+    assert!(!game.has_quit());
+    assert_eq!(game.scene_count(), 2);
+    assert_eq!(log.borrow().last().unwrap(), "quit requested");
+
+    // Quitting directly still works while blocked
+    game.quit();
+    game.step(1. / 60.);
+    assert_eq!(game.scene_count(), 0);
+}
@@ -0,0 +1,84 @@
+use mozart::{
+    game::{
+        input::{InputEvent, KeyCode, KeyMods, Recording},
+        Game,
+    },
+    obj::{Make, Obj, Update},
+    Result,
+};
+
+#[derive(Obj)]
+struct Typing {
+    typed: String,
+}
+
+impl Make for Typing {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            typed: String::new(),
+        })
+    }
+}
+
+impl Update for Typing {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        self.typed.push_str(game.input.text());
+    }
+}
+
+fn game() -> Game {
+    let mut game = Game::new().headless::<Typing>().unwrap();
+    game.set_checksum(|game| game.scene::<Typing>().map_or(0, |s| s.typed.len() as u64));
+    game
+}
+
+#[test]
+fn recordings_with_enter_and_backspace_round_trip() {
+    let mut game = game();
+    game.start_recording();
+    for character in ['h', ' ', 'i'] {
+        game.inject(InputEvent::Char(character));
+    }
+    game.step(1. / 60.);
+    let modifiers = KeyMods::default();
+    game.inject(InputEvent::KeyDown {
+        key: KeyCode::Enter,
+        modifiers,
+    });
+    game.inject(InputEvent::Char('\r'));
+    game.inject(InputEvent::Char('\u{8}'));
+    game.step(1. / 60.);
+    let recording = game.stop_recording().unwrap();
+
+    let text = recording.to_string();
+    assert!(text.contains("char U+000D\nchar U+0008\n"), "{text}");
+    let parsed = Recording::parse(&text).unwrap();
+    assert_eq!(parsed, recording);
+
+    let mut replayed = self::game();
+    replayed.replay(&parsed).unwrap();
+    assert_eq!(replayed.scene::<Typing>().unwrap().typed, "h i");
+}
+
+#[test]
+fn replays_stop_where_the_game_diverges() {
+    let mut game = game();
+    game.start_recording();
+    game.inject(InputEvent::Char('a'));
+    game.step(1. / 60.);
+    game.step(1. / 60.);
+    let mut recording = game.stop_recording().unwrap();
+    recording.frames[1].checksum = Some(99);
+
+    let err = self::game().replay(&recording).unwrap_err();
+    assert!(matches!(
+        err,
+        mozart::Error::ReplayDiverged {
+            frame: 1,
+            expected: 99,
+            found: 1
+        }
+    ));
+}
@@ -0,0 +1,138 @@
+use std::{cell::RefCell, rc::Rc};
+
+use mozart::{
+    game::Game,
+    math::point::{pt2, Pt2},
+    obj::{Exit, Make, Obj, Resize, Update},
+    Result,
+};
+
+type Log = Rc<RefCell<Vec<(&'static str, usize)>>>;
+
+#[derive(Obj)]
+struct Base {
+    log: Log,
+    pushed: bool,
+}
+
+impl Make for Base {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            log: Log::default(),
+            pushed: false,
+        })
+    }
+}
+
+impl Update for Base {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        self.log.borrow_mut().push(("base", game.scene_count()));
+        if !self.pushed {
+            self.pushed = true;
+            game.push_scene_with::<Overlay>(self.log.clone());
+        }
+    }
+}
+
+#[derive(Obj)]
+struct Overlay {
+    log: Log,
+}
+
+impl Make for Overlay {
+    type Config = Log;
+
+    fn make(_game: &mut Game, log: Self::Config) -> Result<Self> {
+        Ok(Self { log })
+    }
+}
+
+impl Update for Overlay {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        self.log.borrow_mut().push(("overlay", game.scene_count()));
+        game.pop_scene();
+    }
+}
+
+#[test]
+fn scene_count_includes_updating_scene() {
+    let mut game = Game::new().headless::<Base>().unwrap();
+    game.step_frames(3, 1. / 60.);
+
+    let base = game.scene::<Base>().unwrap();
+    assert_eq!(
+        *base.log.borrow(),
+        [("base", 1), ("overlay", 2), ("base", 1)]
+    );
+}
+
+#[derive(Obj)]
+struct Empty;
+
+impl Make for Empty {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[test]
+fn popping_the_last_scene_fails() {
+    let mut game = Game::new().headless::<Empty>().unwrap();
+    game.pop_scene();
+    game.step_frames(2, 1. / 60.);
+    assert_eq!(game.scene_count(), 0);
+    assert!(game.take_error().is_some());
+}
+
+thread_local! {
+    static QUIT_LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Obj)]
+struct Quitter;
+
+impl Make for Quitter {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Update for Quitter {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        game.request_quit();
+        game.set_window_size((320, 240));
+        QUIT_LOG.with_borrow_mut(|log| log.push("updated".into()));
+    }
+}
+
+impl Resize for Quitter {
+    fn resize(&mut self, _game: &mut Game, size: Pt2) {
+        QUIT_LOG.with_borrow_mut(|log| log.push(format!("resized to {}x{}", size.x, size.y)));
+    }
+}
+
+impl Exit for Quitter {
+    fn exit(&mut self, _game: &mut Game) {
+        QUIT_LOG.with_borrow_mut(|log| log.push("exited".into()));
+    }
+}
+
+#[test]
+fn quitting_and_resizing_during_update() {
+    let mut game = Game::new().headless::<Quitter>().unwrap();
+    game.step(1. / 60.);
+
+    assert!(game.has_quit());
+    assert_eq!(game.scene_count(), 0);
+    assert_eq!(*game.window_size(), pt2(320., 240.));
+    // The resize waits for the update to finish, and the scene exits after
+    QUIT_LOG.with_borrow(|log| {
+        assert_eq!(*log, ["updated", "resized to 320x240", "exited"]);
+    });
+}
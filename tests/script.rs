@@ -0,0 +1,81 @@
+use mozart::{
+    game::{
+        input::{InputEvent, InputScript, KeyCode},
+        Game,
+    },
+    obj::{Make, Obj, Update},
+    Result,
+};
+
+/// Walks right while D is held, jumps on Space and keeps what was typed.
+#[derive(Obj)]
+struct Player {
+    x: u32,
+    jumps: u32,
+    typed: String,
+}
+
+impl Make for Player {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            x: 0,
+            jumps: 0,
+            typed: String::new(),
+        })
+    }
+}
+
+impl Update for Player {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        if game.input.is_key_down(KeyCode::D) {
+            self.x += 1;
+        }
+        if game.input.is_key_pressed(KeyCode::Space) {
+            self.jumps += 1;
+        }
+        self.typed.push_str(game.input.text());
+    }
+}
+
+const SCRIPT: &str = "
+# jump, then walk right
+0: key_down Space
+1: key_up Space
+2: key_down D
+6: key_up D
+
+# type a line
+7: char o
+7: char k
+7: char U+000D
+";
+
+#[test]
+fn scripts_drive_headless_games() {
+    let script = InputScript::parse(SCRIPT).unwrap();
+    assert_eq!(script.frames(), 8);
+    assert_eq!(script.events()[6], (7, InputEvent::Char('\r')));
+
+    let mut game = Game::new().headless::<Player>().unwrap();
+    game.run_script(&script, 1. / 60.);
+
+    let player = game.scene::<Player>().unwrap();
+    assert_eq!(player.jumps, 1);
+    assert_eq!(player.x, 4);
+    // Enter is left out of typed text
+    assert_eq!(player.typed, "ok");
+}
+
+#[test]
+fn scripts_round_trip_through_text() {
+    let script = InputScript::parse(SCRIPT).unwrap();
+    assert_eq!(InputScript::parse(&script.to_string()).unwrap(), script);
+}
+
+#[test]
+fn bad_lines_report_their_number() {
+    let err = InputScript::parse("0: key_down Space\n\n1: jump").unwrap_err();
+    assert!(matches!(err, mozart::Error::Parse { line: 3, .. }), "{err}");
+}
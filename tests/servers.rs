@@ -0,0 +1,88 @@
+use std::{cell::RefCell, rc::Rc};
+
+use mozart::{
+    game::{server::Server, Game},
+    obj::{Make, Obj, Update},
+    Result,
+};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+struct First(Log);
+struct Second(Log);
+
+impl Server for First {
+    fn pre_update(&mut self, game: &mut Game) {
+        self.0.borrow_mut().push("first pre");
+        // A server can't borrow itself from its own hooks, only others
+        assert!(game.server::<First>().is_none());
+        assert!(game.server::<Second>().is_some());
+    }
+    fn post_update(&mut self, _game: &mut Game) {
+        self.0.borrow_mut().push("first post");
+    }
+}
+
+impl Server for Second {
+    fn pre_update(&mut self, _game: &mut Game) {
+        self.0.borrow_mut().push("second pre");
+    }
+    fn post_update(&mut self, _game: &mut Game) {
+        self.0.borrow_mut().push("second post");
+    }
+}
+
+struct Score(u32);
+
+impl Server for Score {}
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, game: &mut Game, _delta: f32) {
+        if let Some(score) = game.server_mut::<Score>() {
+            score.0 += 1;
+        }
+    }
+}
+
+#[test]
+fn hooks_run_around_the_update_in_order_added() {
+    let log = Log::default();
+    let mut game = Game::new()
+        .add_server(First(log.clone()))
+        .add_server(Second(log.clone()))
+        .headless::<Scene>()
+        .unwrap();
+
+    game.step(1. / 60.);
+    assert_eq!(
+        *log.borrow(),
+        ["first pre", "second pre", "first post", "second post"]
+    );
+}
+
+#[test]
+fn objects_borrow_servers() {
+    let mut game = Game::new()
+        .add_server(Score(0))
+        .headless::<Scene>()
+        .unwrap();
+    game.step_frames(3, 1. / 60.);
+    assert_eq!(game.server::<Score>().unwrap().0, 3);
+
+    // Adding another of the same type replaces it
+    game.add_server(Score(10));
+    game.step(1. / 60.);
+    assert_eq!(game.remove_server::<Score>().unwrap().0, 11);
+    assert!(game.server::<Score>().is_none());
+}
@@ -0,0 +1,89 @@
+use mozart::{
+    game::{
+        assets::{
+            shader::{Shader, ShaderSource},
+            vfs::{Dir, Memory},
+            AssetErrorKind, Vfs,
+        },
+        Game,
+    },
+    gl::headless::RecordingBackend,
+    obj::{sprite::Sprite, Make, Obj},
+    Result,
+};
+
+const GRAYSCALE: &str = "\
+A shader for tests, this line is ignored
+#shader vertex
+attribute vec2 in_pos;
+void main() {}
+#shader fragment
+uniform sampler2D tex;
+void main() {}
+";
+
+#[derive(Obj)]
+struct Scene {
+    plain: Sprite,
+    gray: Sprite,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(game: &mut Game, _: Self::Config) -> Result<Self> {
+        let texture = "examples/assets/sprite.png";
+        Ok(Self {
+            plain: Sprite::make(game, Sprite::cfg_from_texture(texture))?,
+            gray: Sprite::make(
+                game,
+                Sprite::cfg_from_texture(texture).shader("shaders/gray.glsl"),
+            )?,
+        })
+    }
+}
+
+fn game() -> Game {
+    let mut vfs = Vfs::new();
+    vfs.mount("", Dir::new("."), 0);
+    let shaders = Memory::new()
+        .with("gray.glsl", GRAYSCALE.as_bytes())
+        .with("vertex_only.glsl", &b"#shader vertex\nvoid main() {}\n"[..])
+        .with("unknown.glsl", &b"#shader geometry\n"[..]);
+    vfs.mount("shaders", shaders, 0);
+    Game::new().vfs(vfs).headless::<Scene>().unwrap()
+}
+
+#[test]
+fn shader_files_are_split_into_stages() {
+    let mut game = game();
+    let source = game
+        .assets
+        .load::<ShaderSource>("shaders/gray.glsl")
+        .unwrap();
+    assert_eq!(source.vertex, "attribute vec2 in_pos;\nvoid main() {}\n");
+    assert_eq!(source.fragment, "uniform sampler2D tex;\nvoid main() {}\n");
+
+    for path in ["shaders/vertex_only.glsl", "shaders/unknown.glsl"] {
+        let err = game.try_load_gl_asset::<Shader>(path).err().unwrap();
+        assert!(matches!(err.kind, AssetErrorKind::Decode(_)), "{path}");
+    }
+}
+
+#[test]
+fn sprites_draw_with_loaded_shaders() {
+    let mut game = game();
+    game.step(1. / 60.);
+    let shader = game.load_gl_asset::<Shader>("shaders/gray.glsl").unwrap();
+
+    let backend = game.graphics().backend::<RecordingBackend>().unwrap();
+    let calls = &backend.last_frame().unwrap().draw_calls;
+    assert_eq!(calls.len(), 2);
+    assert_ne!(calls[0].shader, shader.gl_shader);
+    assert_eq!(calls[1].shader, shader.gl_shader);
+    let (vertex, fragment) = backend.shader_source(shader.gl_shader).unwrap();
+    assert_eq!(
+        (vertex, fragment),
+        (&*shader.source.vertex, &*shader.source.fragment)
+    );
+}
@@ -0,0 +1,65 @@
+use mozart::{
+    game::{
+        input::{InputEvent, KeyCode, KeyMods},
+        Game,
+    },
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[test]
+fn modifiers_change_at_the_start_of_a_frame() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let ctrl = KeyMods {
+        ctrl: true,
+        ..Default::default()
+    };
+    game.inject(InputEvent::KeyDown {
+        key: KeyCode::S,
+        modifiers: ctrl,
+    });
+    assert!(!game.input.modifiers().ctrl);
+
+    game.step(1. / 60.);
+    assert!(game.input.modifiers().ctrl);
+    assert!(game.input.is_key_pressed(KeyCode::S));
+
+    game.inject(InputEvent::KeyUp {
+        key: KeyCode::S,
+        modifiers: KeyMods::default(),
+    });
+    game.step(1. / 60.);
+    assert!(!game.input.modifiers().ctrl);
+}
+
+#[test]
+fn text_leaves_out_control_characters() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    for character in ['h', 'é', '\u{8}', ' ', '!', '\r'] {
+        game.inject(InputEvent::Char(character));
+    }
+    game.step(1. / 60.);
+    assert_eq!(game.input.text(), "hé !");
+
+    game.step(1. / 60.);
+    assert_eq!(game.input.text(), "");
+}
+
+#[test]
+fn headless_games_have_their_own_clipboard() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    assert_eq!(game.clipboard(), None);
+    game.set_clipboard("copied");
+    assert_eq!(game.clipboard().as_deref(), Some("copied"));
+}
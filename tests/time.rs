@@ -0,0 +1,101 @@
+use mozart::{
+    game::Game,
+    obj::{FixedUpdate, Make, Obj, Update},
+    Result,
+};
+
+#[derive(Obj)]
+#[process_mode(Always)]
+struct Menu {
+    time: f32,
+}
+
+impl Update for Menu {
+    fn update(&mut self, _game: &mut Game, delta: f32) {
+        self.time += delta;
+    }
+}
+
+#[derive(Obj)]
+struct Scene {
+    menu: Menu,
+    time: f32,
+    fixed_steps: u32,
+}
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            menu: Menu { time: 0. },
+            time: 0.,
+            fixed_steps: 0,
+        })
+    }
+}
+
+impl Update for Scene {
+    fn update(&mut self, _game: &mut Game, delta: f32) {
+        self.time += delta;
+    }
+}
+
+impl FixedUpdate for Scene {
+    fn fixed_update(&mut self, _game: &mut Game, _delta: f32) {
+        self.fixed_steps += 1;
+    }
+}
+
+/// A pause menu over a paused game.
+#[derive(Obj)]
+#[process_mode(WhenPaused)]
+struct Paused {
+    menu: Menu,
+}
+
+impl Make for Paused {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self {
+            menu: Menu { time: 0. },
+        })
+    }
+}
+
+#[test]
+fn always_objects_get_unscaled_time() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    game.set_time_scale(0.5);
+    game.step_frames(4, 0.25);
+
+    let scene = game.scene::<Scene>().unwrap();
+    assert_eq!(scene.time, 0.5);
+    assert_eq!(scene.menu.time, 1.);
+}
+
+#[test]
+fn paused_menus_run_at_time_scale_zero() {
+    let mut game = Game::new().headless::<Paused>().unwrap();
+    game.set_paused(true);
+    game.set_time_scale(0.);
+    game.step_frames(2, 0.5);
+    assert_eq!(game.scene::<Paused>().unwrap().menu.time, 1.);
+}
+
+#[test]
+fn pause_holds_fixed_updates() {
+    let mut game = Game::new().fixed_timestep(10.).headless::<Scene>().unwrap();
+    game.step_frames(3, 0.1);
+    assert_eq!(game.scene::<Scene>().unwrap().fixed_steps, 3);
+
+    game.set_paused(true);
+    game.step_frames(5, 0.1);
+    assert_eq!(game.scene::<Scene>().unwrap().fixed_steps, 3);
+
+    // No burst of steps for the paused time
+    game.set_paused(false);
+    game.step(0.1);
+    assert_eq!(game.scene::<Scene>().unwrap().fixed_steps, 4);
+}
@@ -0,0 +1,103 @@
+use std::{cell::RefCell, rc::Rc};
+
+use mozart::{
+    game::Game,
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+fn log(log: &Log, name: &'static str) -> impl FnMut(&mut Game) + 'static {
+    let log = log.clone();
+    move |_| log.borrow_mut().push(name)
+}
+
+#[test]
+fn timers_due_in_one_frame_fire_in_order() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let fired = Log::default();
+    game.timers.after(0.5, log(&fired, "last"));
+    game.timers.after(0.25, log(&fired, "first"));
+    game.timers.every(0.25, log(&fired, "repeat"));
+    game.timers.after(0.25, log(&fired, "second"));
+
+    game.step(0.5);
+    // By due time, then by creation order
+    assert_eq!(
+        *fired.borrow(),
+        ["first", "repeat", "second", "last", "repeat"]
+    );
+}
+
+#[test]
+fn dropping_the_handle_cancels_the_timer() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let fired = Log::default();
+    let id = game.timers.after(0.25, log(&fired, "dropped"));
+    let timer = game.timers.owned(id).unwrap();
+    let kept = game.timers.once(0.25);
+
+    drop(timer);
+    game.step(0.5);
+    assert!(fired.borrow().is_empty());
+    assert!(!game.timers.is_active(id));
+    assert!(kept.finished());
+}
+
+#[test]
+fn handles_report_when_they_fire() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let once = game.timers.once(0.25);
+    let repeating = game.timers.repeating(0.125);
+
+    game.step(0.125);
+    assert!(!once.just_fired());
+    assert_eq!(repeating.fired_count(), 1);
+
+    game.step(0.25);
+    assert!(once.just_fired() && once.finished());
+    assert_eq!(repeating.fired_count(), 2);
+
+    game.step(0.0625);
+    assert!(!once.just_fired() && once.finished());
+    assert!(!repeating.just_fired() && !repeating.finished());
+}
+
+#[test]
+fn callbacks_can_cancel_timers_due_in_the_same_frame() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let fired = Log::default();
+    let later = game.timers.after(0.5, log(&fired, "cancelled"));
+    game.timers
+        .after(0.25, move |game| game.timers.cancel(later));
+
+    game.step(1.);
+    assert!(fired.borrow().is_empty());
+}
+
+#[test]
+fn timers_wait_while_paused() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    let timer = game.timers.once(0.5);
+
+    game.set_paused(true);
+    game.step_frames(4, 0.25);
+    assert!(!timer.finished());
+    assert_eq!(game.timers.remaining(timer.id()), Some(0.5));
+
+    game.set_paused(false);
+    game.step_frames(2, 0.25);
+    assert!(timer.finished());
+}
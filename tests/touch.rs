@@ -0,0 +1,200 @@
+use mozart::{
+    game::{
+        input::{Gesture, InputEvent, MouseButton, TouchPhase},
+        Game,
+    },
+    math::point::{pt2, Pt2},
+    obj::{Make, Obj},
+    Result,
+};
+
+#[derive(Obj)]
+struct Scene;
+
+impl Make for Scene {
+    type Config = ();
+
+    fn make(_game: &mut Game, _: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+const FRAME: f32 = 1. / 60.;
+
+fn touch(game: &mut Game, id: u64, phase: TouchPhase, position: Pt2) {
+    game.inject(InputEvent::Touch {
+        id,
+        phase,
+        position,
+    });
+}
+
+#[test]
+fn touches_move_through_their_phases() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 7, TouchPhase::Started, pt2(10., 10.));
+    game.step(FRAME);
+    let started = *game.input.touch(7).unwrap();
+    assert_eq!(started.phase, TouchPhase::Started);
+    assert_eq!(started.start_position, pt2(10., 10.));
+    // The first finger also holds the left mouse button
+    assert!(game.input.is_mouse_pressed(MouseButton::Left));
+    assert_eq!(game.input.mouse_position(), pt2(10., 10.));
+
+    game.step(FRAME);
+    assert_eq!(game.input.touch(7).unwrap().phase, TouchPhase::Stationary);
+
+    touch(&mut game, 7, TouchPhase::Moved, pt2(30., 10.));
+    game.step(FRAME);
+    let moved = *game.input.touch(7).unwrap();
+    assert_eq!(moved.phase, TouchPhase::Moved);
+    assert_eq!(moved.previous_position, pt2(10., 10.));
+    assert_eq!(moved.position, pt2(30., 10.));
+    assert_eq!(moved.start_position, pt2(10., 10.));
+    assert_eq!(game.input.mouse_position(), pt2(30., 10.));
+
+    // Ended touches stay for the frame they ended in
+    touch(&mut game, 7, TouchPhase::Ended, pt2(30., 10.));
+    game.step(FRAME);
+    let ended = *game.input.touch(7).unwrap();
+    assert_eq!(ended.phase, TouchPhase::Ended);
+    assert!(!ended.is_active());
+    assert!(game.input.is_mouse_released(MouseButton::Left));
+
+    game.step(FRAME);
+    assert!(game.input.touches().is_empty());
+}
+
+#[test]
+fn only_the_first_finger_emulates_the_mouse() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 1, TouchPhase::Started, pt2(10., 10.));
+    touch(&mut game, 2, TouchPhase::Started, pt2(50., 50.));
+    game.step(FRAME);
+    let ids: Vec<u64> = game.input.touches().iter().map(|t| t.id).collect();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(game.input.mouse_position(), pt2(10., 10.));
+
+    // Lifting the second finger leaves the mouse down
+    touch(&mut game, 2, TouchPhase::Ended, pt2(50., 50.));
+    game.step(FRAME);
+    assert!(game.input.is_mouse_down(MouseButton::Left));
+
+    game.input.set_touch_emulates_mouse(false);
+    touch(&mut game, 1, TouchPhase::Ended, pt2(10., 10.));
+    touch(&mut game, 3, TouchPhase::Started, pt2(90., 90.));
+    game.step(FRAME);
+    assert_eq!(game.input.mouse_position(), pt2(10., 10.));
+}
+
+#[test]
+fn quick_touches_tap() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 0, TouchPhase::Started, pt2(20., 20.));
+    game.step(FRAME);
+    touch(&mut game, 0, TouchPhase::Moved, pt2(22., 21.));
+    touch(&mut game, 0, TouchPhase::Ended, pt2(22., 21.));
+    game.step(FRAME);
+    assert_eq!(
+        game.input.gestures(),
+        [Gesture::Tap {
+            position: pt2(22., 21.)
+        }]
+    );
+
+    // Gestures last one frame
+    game.step(FRAME);
+    assert!(game.input.gestures().is_empty());
+}
+
+#[test]
+fn slow_touches_long_press_instead_of_tapping() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 0, TouchPhase::Started, pt2(20., 20.));
+    game.step(FRAME);
+    game.step(Gesture::LONG_PRESS_TIME as f32);
+    assert_eq!(
+        game.input.gestures(),
+        [Gesture::LongPress {
+            position: pt2(20., 20.)
+        }]
+    );
+
+    // Recognized once, and lifting the finger isn't a tap
+    game.step(FRAME);
+    touch(&mut game, 0, TouchPhase::Ended, pt2(20., 20.));
+    game.step(FRAME);
+    assert!(game.input.gestures().is_empty());
+}
+
+#[test]
+fn dragging_is_not_a_tap() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 0, TouchPhase::Started, pt2(0., 0.));
+    game.step(FRAME);
+    // Slowly, so it isn't a swipe either
+    for x in 1..=10 {
+        touch(&mut game, 0, TouchPhase::Moved, pt2(x as f32 * 10., 0.));
+        game.step(0.1);
+        assert!(game.input.gestures().is_empty());
+    }
+    touch(&mut game, 0, TouchPhase::Moved, pt2(100., 0.));
+    touch(&mut game, 0, TouchPhase::Ended, pt2(100., 0.));
+    game.step(FRAME);
+    assert!(game.input.gestures().is_empty());
+}
+
+#[test]
+fn quick_drags_swipe() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 0, TouchPhase::Started, pt2(0., 0.));
+    game.step(FRAME);
+    touch(&mut game, 0, TouchPhase::Moved, pt2(40., 0.));
+    game.step(FRAME);
+    touch(&mut game, 0, TouchPhase::Moved, pt2(80., 0.));
+    touch(&mut game, 0, TouchPhase::Ended, pt2(80., 0.));
+    game.step(FRAME);
+    assert_eq!(
+        game.input.gestures(),
+        [Gesture::Swipe {
+            start: pt2(0., 0.),
+            end: pt2(80., 0.),
+        }]
+    );
+}
+
+#[test]
+fn two_fingers_pinch() {
+    let mut game = Game::new().headless::<Scene>().unwrap();
+    touch(&mut game, 0, TouchPhase::Started, pt2(40., 50.));
+    touch(&mut game, 1, TouchPhase::Started, pt2(60., 50.));
+    game.step(FRAME);
+    assert!(game.input.gestures().is_empty());
+
+    // Spread from 20 pixels apart to 40
+    touch(&mut game, 0, TouchPhase::Moved, pt2(30., 50.));
+    touch(&mut game, 1, TouchPhase::Moved, pt2(70., 50.));
+    game.step(FRAME);
+    assert_eq!(
+        game.input.gestures(),
+        [Gesture::Pinch {
+            center: pt2(50., 50.),
+            scale: 2.,
+        }]
+    );
+
+    // Holding still doesn't pinch
+    game.step(FRAME);
+    assert!(game.input.gestures().is_empty());
+
+    // Squeeze back to 20 pixels apart, moving one finger
+    touch(&mut game, 1, TouchPhase::Moved, pt2(50., 50.));
+    game.step(FRAME);
+    assert_eq!(
+        game.input.gestures(),
+        [Gesture::Pinch {
+            center: pt2(40., 50.),
+            scale: 0.5,
+        }]
+    );
+}